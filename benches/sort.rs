@@ -11,7 +11,7 @@ mod unstable {
 
     use quicksort::quicksort_by;
 
-    use rand::{Rand, Rng, XorShiftRng};
+    use rand::{Rng, XorShiftRng};
 
     use rdxsort::*;
 
@@ -19,6 +19,42 @@ mod unstable {
     static N_MEDIUM: usize = 10_000;
     static N_LARGE:  usize = 100_000;
 
+    /// Random data generation, `rand` does not cover all benchmarked types.
+    trait BenchRand: Sized {
+        fn gen_data(n: usize) -> Vec<Self>;
+    }
+
+    macro_rules! bench_rand {
+        ($($t:ty),*) => {
+            $(
+                impl BenchRand for $t {
+                    fn gen_data(n: usize) -> Vec<$t> {
+                        let mut rng = XorShiftRng::new_unseeded();
+                        rng.gen_iter::<$t>().take(n).collect()
+                    }
+                }
+            )*
+        }
+    }
+
+    bench_rand!(bool, char, f32, f64, i8, i16, i32, i64, u8, u16, u32, u64);
+
+    impl BenchRand for u128 {
+        fn gen_data(n: usize) -> Vec<u128> {
+            let mut rng = XorShiftRng::new_unseeded();
+            rng.gen_iter::<(u64, u64)>()
+                .take(n)
+                .map(|(hi, lo)| ((hi as u128) << 64) | (lo as u128))
+                .collect()
+        }
+    }
+
+    impl BenchRand for i128 {
+        fn gen_data(n: usize) -> Vec<i128> {
+            u128::gen_data(n).into_iter().map(|x| x as i128).collect()
+        }
+    }
+
     fn bench_exe<T, F>(b: &mut Bencher, data: Vec<T>, f: F) where T: Clone, F: Fn(Vec<T>) {
         let _ = b.iter(|| {
             let data2 = data.clone();
@@ -27,19 +63,18 @@ mod unstable {
     }
 
     fn bench_generic<T, F>(b: &mut Bencher, f: F, n: usize)
-        where T: Clone + PartialOrd + Rand,
+        where T: Clone + PartialOrd + BenchRand,
               F: Fn(Vec<T>)
     {
         // generate data
-        let mut rng = XorShiftRng::new_unseeded();
-        let data: Vec<T> = rng.gen_iter::<T>().take(n).collect();
+        let data: Vec<T> = T::gen_data(n);
 
         // run benchmark
         bench_exe(b, data, f);
     }
 
     fn bench_quicksort_generic<T>(b: &mut Bencher, n: usize)
-        where T: Clone + PartialOrd + Rand,
+        where T: Clone + PartialOrd + BenchRand,
               Vec<T>: RdxSort
     {
         bench_generic(b, |data| {
@@ -49,7 +84,7 @@ mod unstable {
     }
 
     fn bench_std_generic<T>(b: &mut Bencher, n: usize)
-        where T: Clone + PartialOrd + Rand,
+        where T: Clone + PartialOrd + BenchRand,
               Vec<T>: RdxSort
     {
         bench_generic(b, |data| {
@@ -59,7 +94,7 @@ mod unstable {
     }
 
     fn bench_rdxsort_generic<T>(b: &mut Bencher, n: usize)
-        where T: Clone + PartialOrd + Rand,
+        where T: Clone + PartialOrd + BenchRand,
               Vec<T>: RdxSort
     {
         bench_generic(b, |data| {
@@ -68,6 +103,17 @@ mod unstable {
         }, n);
     }
 
+    fn bench_rdxsort_by_key_generic<T, K, F>(b: &mut Bencher, n: usize, f: F)
+        where T: Clone + PartialOrd + BenchRand,
+              K: Rdx,
+              F: Fn(&T) -> K
+    {
+        bench_generic(b, |data| {
+            let mut data = data;
+            data.rdxsort_by_key(&f);
+        }, n);
+    }
+
     macro_rules! bench_type {
         ($t:ty, [
             $fn_small_quicksort:ident,
@@ -223,6 +269,18 @@ mod unstable {
         bench_large_i64_std
     ]);
 
+    bench_type!(i128, [
+        bench_small_i128_quicksort,
+        bench_small_i128_rdxsort,
+        bench_small_i128_std,
+        bench_medium_i128_quicksort,
+        bench_medium_i128_rdxsort,
+        bench_medium_i128_std,
+        bench_large_i128_quicksort,
+        bench_large_i128_rdxsort,
+        bench_large_i128_std
+    ]);
+
     bench_type!(u8, [
         bench_small_u8_quicksort,
         bench_small_u8_rdxsort,
@@ -270,4 +328,92 @@ mod unstable {
         bench_large_u64_rdxsort,
         bench_large_u64_std
     ]);
+
+    bench_type!(u128, [
+        bench_small_u128_quicksort,
+        bench_small_u128_rdxsort,
+        bench_small_u128_std,
+        bench_medium_u128_quicksort,
+        bench_medium_u128_rdxsort,
+        bench_medium_u128_std,
+        bench_large_u128_quicksort,
+        bench_large_u128_rdxsort,
+        bench_large_u128_std
+    ]);
+
+    // digit width trade-off: more buckets means less rounds, but larger histograms
+    mod radix {
+        use super::*;
+
+        macro_rules! bench_radix {
+            ($t:ty, $n:expr, [
+                $fn_radix4:ident,
+                $fn_radix8:ident,
+                $fn_radix11:ident,
+                $fn_radix16:ident
+            ]) => {
+                #[bench]
+                fn $fn_radix4(b: &mut Bencher) {
+                    bench_rdxsort_generic::<$t>(b, $n);
+                }
+
+                #[bench]
+                fn $fn_radix8(b: &mut Bencher) {
+                    bench_rdxsort_by_key_generic(b, $n, |&x: &$t| Radix8(x));
+                }
+
+                #[bench]
+                fn $fn_radix11(b: &mut Bencher) {
+                    bench_rdxsort_by_key_generic(b, $n, |&x: &$t| Radix11(x));
+                }
+
+                #[bench]
+                fn $fn_radix16(b: &mut Bencher) {
+                    bench_rdxsort_by_key_generic(b, $n, |&x: &$t| Radix16(x));
+                }
+            };
+        }
+
+        bench_radix!(u32, N_SMALL, [
+            bench_small_u32_radix4,
+            bench_small_u32_radix8,
+            bench_small_u32_radix11,
+            bench_small_u32_radix16
+        ]);
+
+        bench_radix!(u32, N_MEDIUM, [
+            bench_medium_u32_radix4,
+            bench_medium_u32_radix8,
+            bench_medium_u32_radix11,
+            bench_medium_u32_radix16
+        ]);
+
+        bench_radix!(u32, N_LARGE, [
+            bench_large_u32_radix4,
+            bench_large_u32_radix8,
+            bench_large_u32_radix11,
+            bench_large_u32_radix16
+        ]);
+
+        bench_radix!(u64, N_SMALL, [
+            bench_small_u64_radix4,
+            bench_small_u64_radix8,
+            bench_small_u64_radix11,
+            bench_small_u64_radix16
+        ]);
+
+        bench_radix!(u64, N_MEDIUM, [
+            bench_medium_u64_radix4,
+            bench_medium_u64_radix8,
+            bench_medium_u64_radix11,
+            bench_medium_u64_radix16
+        ]);
+
+        bench_radix!(u64, N_LARGE, [
+            bench_large_u64_radix4,
+            bench_large_u64_radix8,
+            bench_large_u64_radix11,
+            bench_large_u64_radix16
+        ]);
+    }
 }
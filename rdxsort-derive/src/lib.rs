@@ -0,0 +1,202 @@
+//! # RdxSort Derive
+//!
+//! Provides `#[derive(Rdx)]` for the [rdxsort](https://crates.io/crates/rdxsort) crate. Usually,
+//! this crate is used via the `derive` feature of `rdxsort`.
+//!
+//! - **structs:** use the implementations of their fields, where the first field is the most
+//!   significant one. This is the same order as `#[derive(Ord)]` uses.
+//! - **enums without fields:** one bucket per variant, ordered by their discriminants. These are
+//!   compared as `i128`, or as `u128` for `#[repr(u128)]` enums.
+//!
+//! ```
+//! extern crate rdxsort;
+//! #[macro_use]
+//! extern crate rdxsort_derive;
+//!
+//! use rdxsort::*;
+//!
+//! #[derive(Clone, Debug, PartialEq, Rdx)]
+//! struct Foo {
+//!     a: u8,
+//!     b: i16,
+//! }
+//!
+//! fn main() {
+//!     let mut data = vec![Foo{a: 1, b: -3}, Foo{a: 0, b: 7}, Foo{a: 1, b: -4}];
+//!     data.rdxsort();
+//!     assert!(data == vec![Foo{a: 0, b: 7}, Foo{a: 1, b: -4}, Foo{a: 1, b: -3}]);
+//! }
+//! ```
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DeriveInput, Fields, Index, Meta, Token};
+use syn::punctuated::Punctuated;
+
+#[proc_macro_derive(Rdx)]
+pub fn derive_rdx(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let result = match input.data {
+        Data::Struct(ref data) => derive_struct(&input, &data.fields),
+        Data::Enum(ref data) => derive_enum(&input, data),
+        Data::Union(_) => Err(syn::Error::new_spanned(&input.ident,
+                                                      "`Rdx` cannot be derived for unions")),
+    };
+    result.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    // fields in declaration order, the first one is the most significant
+    let types: Vec<&syn::Type> = fields.iter().map(|f| &f.ty).collect();
+    let accessors: Vec<TokenStream2> = fields.iter()
+        .enumerate()
+        .map(|(i, f)| match f.ident {
+            Some(ref ident) => quote!(#ident),
+            None => {
+                let i = Index::from(i);
+                quote!(#i)
+            }
+        })
+        .collect();
+
+    let mut generics = input.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for ty in types.iter() {
+            where_clause.predicates.push(syn::parse_quote!(#ty: ::rdxsort::Rdx));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // nested checks from the inside out: the most significant (first) field is checked last,
+    // the least significant (last) field first, like the tuple impls do
+    let mut get_bucket = quote!(unreachable!());
+    let mut reverse = quote!(unreachable!());
+    for (ty, accessor) in types.iter().zip(accessors.iter()) {
+        get_bucket = quote! {
+            {
+                let nrounds = <#ty as ::rdxsort::Rdx>::CFG_NROUNDS;
+                if round < nrounds {
+                    ::rdxsort::Rdx::get_bucket(&self.#accessor, round).cast()
+                } else {
+                    let round = round - nrounds;
+                    #get_bucket
+                }
+            }
+        };
+        reverse = quote! {
+            {
+                let nrounds = <#ty as ::rdxsort::Rdx>::CFG_NROUNDS;
+                if round < nrounds {
+                    <#ty as ::rdxsort::Rdx>::reverse(round, bucket)
+                } else {
+                    let round = round - nrounds;
+                    #reverse
+                }
+            }
+        };
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::rdxsort::Rdx for #name #ty_generics #where_clause {
+            const CFG_NBUCKETS: usize = {
+                let mut nbuckets = 0usize;
+                #(
+                    let n = <#types as ::rdxsort::Rdx>::CFG_NBUCKETS;
+                    if n > nbuckets {
+                        nbuckets = n;
+                    }
+                )*
+                nbuckets
+            };
+
+            const CFG_NROUNDS: usize = 0usize #( + <#types as ::rdxsort::Rdx>::CFG_NROUNDS )*;
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn get_bucket(&self, round: usize) -> ::rdxsort::Digit<Self> {
+                #get_bucket
+            }
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn reverse(round: usize, bucket: usize) -> bool {
+                #reverse
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput, data: &syn::DataEnum) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    for variant in data.variants.iter() {
+        match variant.fields {
+            Fields::Unit => {}
+            _ => {
+                return Err(syn::Error::new_spanned(variant,
+                                                   "`Rdx` can only be derived for enums \
+                                                    without fields"));
+            }
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants: Vec<&syn::Ident> = data.variants.iter().map(|v| &v.ident).collect();
+    let discriminant = discriminant_type(input)?;
+    let nvariants = variants.len();
+    let nrounds = if nvariants == 0 { 0usize } else { 1usize };
+
+    // the bucket is the rank of the discriminant, which the compiler can evaluate upfront. Ranks
+    // are computed through `as i128`, except for `#[repr(u128)]` enums (see `discriminant_type`)
+    let ranks: Vec<TokenStream2> = variants.iter()
+        .map(|v| {
+            quote! {
+                0usize #(
+                    + (((#name::#variants as #discriminant) < (#name::#v as #discriminant))
+                       as usize)
+                )*
+            }
+        })
+        .collect();
+
+    Ok(quote! {
+        impl #impl_generics ::rdxsort::Rdx for #name #ty_generics #where_clause {
+            const CFG_NBUCKETS: usize = #nvariants;
+
+            const CFG_NROUNDS: usize = #nrounds;
+
+            #[inline]
+            #[allow(unused_variables, unreachable_code)]
+            fn get_bucket(&self, round: usize) -> ::rdxsort::Digit<Self> {
+                ::rdxsort::Digit::new(match *self {
+                    #( #name::#variants => #ranks, )*
+                })
+            }
+
+            #[inline]
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
+            }
+        }
+    })
+}
+
+/// Returns the type that can hold all discriminants of the enum. This is `i128`, except for
+/// `#[repr(u128)]` enums, where discriminants above `i128::MAX` would wrap around.
+fn discriminant_type(input: &DeriveInput) -> syn::Result<syn::Ident> {
+    let mut unsigned = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        unsigned |= metas.iter().any(|meta| meta.path().is_ident("u128"));
+    }
+    let name = if unsigned { "u128" } else { "i128" };
+    Ok(syn::Ident::new(name, proc_macro2::Span::call_site()))
+}
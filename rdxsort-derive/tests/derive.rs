@@ -0,0 +1,172 @@
+extern crate rand;
+extern crate rdxsort;
+extern crate rdxsort_derive;
+
+use rand::{Rng, XorShiftRng};
+
+use rdxsort::{RdxSort, RdxSortByKey};
+use rdxsort_derive::Rdx;
+
+pub const CFG_N: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+struct Named {
+    a: bool,
+    b: i16,
+    c: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Rdx)]
+struct Unnamed(i8, f32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+struct Unit;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+struct Generic<T> {
+    x: T,
+    y: Option<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+enum Kind {
+    Low,
+    High,
+    Medium,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+enum Explicit {
+    A = 10,
+    B = -3,
+    C = 7,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+#[repr(u128)]
+enum Wide {
+    A = u128::MAX,
+    B = 1,
+    C = 1 << 127,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+enum Empty {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Rdx)]
+struct Nested {
+    kind: Kind,
+    named: Named,
+}
+
+fn test_generic<T>(data: Vec<T>)
+    where T: Clone + PartialOrd,
+          Vec<T>: RdxSort
+{
+    let mut data = data;
+    let mut data_sorted_ref = data.clone();
+    data_sorted_ref.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    data.rdxsort();
+    assert!(data == data_sorted_ref, "sortd data does not match the reference!");
+}
+
+fn gen_named(rng: &mut XorShiftRng) -> Named {
+    Named {
+        a: rng.gen(),
+        b: rng.gen(),
+        c: rng.gen(),
+    }
+}
+
+fn gen_kind(rng: &mut XorShiftRng) -> Kind {
+    *rng.choose(&[Kind::Low, Kind::High, Kind::Medium]).unwrap()
+}
+
+#[test]
+fn test_rnd_named() {
+    let mut rng = XorShiftRng::new_unseeded();
+    test_generic((0..CFG_N).map(|_| gen_named(&mut rng)).collect());
+}
+
+#[test]
+fn test_rnd_unnamed() {
+    let mut rng = XorShiftRng::new_unseeded();
+    test_generic((0..CFG_N).map(|_| Unnamed(rng.gen(), rng.gen::<f32>() - 0.5f32)).collect());
+}
+
+#[test]
+fn test_unit() {
+    assert_eq!(<Unit as rdxsort::Rdx>::CFG_NROUNDS, 0);
+    test_generic(vec![Unit; 10]);
+}
+
+#[test]
+fn test_rnd_generic() {
+    let mut rng = XorShiftRng::new_unseeded();
+    test_generic((0..CFG_N)
+        .map(|_| {
+            Generic {
+                x: rng.gen::<i32>() % 10,
+                y: if rng.gen() { Some(rng.gen()) } else { None },
+            }
+        })
+        .collect());
+}
+
+#[test]
+fn test_rnd_enum() {
+    let mut rng = XorShiftRng::new_unseeded();
+    assert_eq!(<Kind as rdxsort::Rdx>::CFG_NBUCKETS, 3);
+    assert_eq!(<Kind as rdxsort::Rdx>::CFG_NROUNDS, 1);
+    test_generic((0..CFG_N).map(|_| gen_kind(&mut rng)).collect());
+}
+
+#[test]
+fn test_rnd_explicit_enum() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<Explicit> = (0..CFG_N)
+        .map(|_| *rng.choose(&[Explicit::A, Explicit::B, Explicit::C]).unwrap())
+        .collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_rnd_wide_enum() {
+    // discriminants above `i128::MAX` must not wrap around
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<Wide> = (0..CFG_N)
+        .map(|_| *rng.choose(&[Wide::A, Wide::B, Wide::C]).unwrap())
+        .collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_empty_enum() {
+    assert_eq!(<Empty as rdxsort::Rdx>::CFG_NROUNDS, 0);
+    test_generic(Vec::<Empty>::new());
+}
+
+#[test]
+fn test_rnd_nested() {
+    let mut rng = XorShiftRng::new_unseeded();
+    test_generic((0..CFG_N)
+        .map(|_| {
+            Nested {
+                kind: gen_kind(&mut rng),
+                named: gen_named(&mut rng),
+            }
+        })
+        .collect());
+}
+
+#[test]
+fn test_rnd_by_key() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut data: Vec<(Kind, usize)> = (0..CFG_N).map(|i| (gen_kind(&mut rng), i)).collect();
+    let mut data_sorted_ref = data.clone();
+    data_sorted_ref.sort_by_key(|x| x.0);
+
+    data.rdxsort_by_key(|x| x.0);
+    assert!(data == data_sorted_ref, "sortd data does not match the reference!");
+}
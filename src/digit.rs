@@ -0,0 +1,86 @@
+use super::Rdx;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+/// Bucket of an element for a single round, see `Rdx::get_bucket`.
+///
+/// A digit is always smaller than `T::CFG_NBUCKETS`. This is checked when the digit is created,
+/// which the compiler can usually prove at compile time (e.g. for masked values), so the sorting
+/// algorithms do not need to check it again.
+pub struct Digit<T> {
+    value: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Digit<T>
+    where T: Rdx
+{
+    /// Creates a new digit.
+    ///
+    /// **Panics** if `value >= T::CFG_NBUCKETS`.
+    #[inline]
+    pub fn new(value: usize) -> Digit<T> {
+        assert!(value < T::CFG_NBUCKETS,
+                "Your Rdx implementation returns a bucket >= CFG_NBUCKETS!");
+        Digit {
+            value,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the bucket index.
+    #[inline]
+    pub fn get(self) -> usize {
+        self.value
+    }
+
+    /// Converts the digit to a digit of another type, e.g. of a type that wraps `T`.
+    ///
+    /// **Panics** if the value is out of range for `U`, which cannot happen if `U` uses at least
+    /// as many buckets as `T`.
+    #[inline]
+    pub fn cast<U>(self) -> Digit<U>
+        where U: Rdx
+    {
+        if T::CFG_NBUCKETS <= U::CFG_NBUCKETS {
+            Digit {
+                value: self.value,
+                phantom: PhantomData,
+            }
+        } else {
+            Digit::new(self.value)
+        }
+    }
+
+    /// Returns the digit that is at the same position when counting from the last bucket.
+    #[inline]
+    pub fn mirror(self) -> Digit<T> {
+        Digit {
+            value: T::CFG_NBUCKETS - 1 - self.value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for Digit<T> {
+    fn clone(&self) -> Digit<T> {
+        *self
+    }
+}
+
+impl<T> Copy for Digit<T> {}
+
+impl<T> PartialEq for Digit<T> {
+    fn eq(&self, other: &Digit<T>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Digit<T> {}
+
+impl<T> fmt::Debug for Digit<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Digit({})", self.value)
+    }
+}
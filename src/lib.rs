@@ -20,12 +20,27 @@
 //!
 //! - **bool:** simple split into 2 chunks
 //! - **char:** behaves like `u32`
-//! - **unsigned integers:** native implementation, depending on the width
+//! - **unsigned integers:** native implementation, depending on the width. `u128` uses digits of
+//!   8 bits
 //! - **signed integers:** splitting into positive and negative parts and using the unsigned
 //!   implementation
 //! - **floats:** splitting of data into positive and negative numbers and treating the two
 //!   ranges as unsigned integer values. `NaN`s are not supported!
-//! - **arrays, tuples:** use the implementation of the inner data types
+//! - **`TotalOrd`, `NanLast`:** wrappers for floats that support `NaN`s. `TotalOrd` matches the
+//!   order of `total_cmp`, `NanLast` puts all `NaN`s last, e.g.
+//!   `data.rdxsort_by_key(|&x| TotalOrd(x))`
+//! - **arrays (of any length), tuples:** use the implementation of the inner data types
+//! - **`String`, `&str`, `Vec<u8>`, `&[u8]`:** variable-length keys, sorted byte-wise using MSD
+//!   Radix Sort. The result matches their lexicographic order
+//! - **`Radix8`, `Radix11`, `Radix16`:** wrappers for unsigned integers that use wider digits,
+//!   which means less rounds but larger histograms. `Radix8` is usually faster than the default
+//!   implementation for 32 and 64 bits, e.g. `data.rdxsort_by_key(|&x| Radix8(x))`. `Radix8`
+//!   also wraps byte arrays `[u8; N]`, which are then sorted one byte per round
+//! - **`std::cmp::Reverse`:** inverts the order of the inner data type, see also
+//!   `RdxSort::rdxsort_desc`
+//! - **`NonZero*`, `std::num::Wrapping`:** behave like the inner data type
+//! - **`Option`:** uses the implementation of the inner data type plus one round, `None` is sorted
+//!   before `Some`
 //! - *custom data types...: fill in the provided template trait*
 //!
 //!
@@ -41,6 +56,58 @@
 //! }
 //! ```
 //!
+//! Elements can also be sorted by a key, in which case only the key type has to implement `Rdx`:
+//!
+//! ```
+//! use rdxsort::*;
+//!
+//! fn main() {
+//!     let mut data = vec![("b", 2u64), ("c", 0u64), ("a", 2u64)];
+//!     data.rdxsort_by_key(|x| x.1);
+//!     assert!(data == vec![("c", 0u64), ("b", 2u64), ("a", 2u64)]);
+//! }
+//! ```
+//!
+//! Keys that are stored apart from their payload can be sorted together with it. The payload
+//! neither has to implement `Rdx` nor `Clone`:
+//!
+//! ```
+//! use rdxsort::*;
+//!
+//! fn main() {
+//!     let mut keys = vec![3u32, 1, 2];
+//!     let mut names = vec!["c", "a", "b"];
+//!     keys.rdxsort_with(&mut [&mut names]);
+//!     assert!(keys == vec![1, 2, 3]);
+//!     assert!(names == vec!["a", "b", "c"]);
+//! }
+//! ```
+//!
+//! Sorting many small data sets can reuse the buffers of a `RdxSorter`, so repeated sorts do not
+//! allocate:
+//!
+//! ```
+//! use rdxsort::*;
+//!
+//! fn main() {
+//!     let mut sorter = RdxSorter::with_capacity(4);
+//!     for _ in 0..10 {
+//!         let mut data = vec![3u32, 1, 4, 2];
+//!         sorter.sort(&mut data);
+//!         assert!(data == vec![1, 2, 3, 4]);
+//!     }
+//! }
+//! ```
+//!
+//! When the `rayon` feature is enabled, `RdxParSort::par_rdxsort` sorts large data sets using
+//! multiple threads. The result is the same as for `RdxSort::rdxsort`.
+//!
+//! Besides sorting, `RdxTree` and `RdxMap` provide an ordered set and an ordered map based on a
+//! radix tree. Their elements are ordered the same way `RdxSort::rdxsort` would sort them.
+//!
+//! The crate only requires `core` and `alloc`. Disabling the default `std` feature makes it
+//! `#![no_std]`, which drops `RdxTree::print` and the `rayon` support.
+//!
 //!
 //! ## Performance
 //!
@@ -60,64 +127,73 @@
 //!
 //! ### Small (1'000 elements)
 //!
-//! For small data sets Radix Sort can be an advantage for data types with up to 32 bits size. For
-//! 64 bits, standard library sorting should be preferred.
+//! Small data sets are sorted by a stable MSD variant, which finishes most buckets after the first
+//! pass using insertion sort instead of going through all rounds. This makes Radix Sort the better
+//! choice for most types, including `u64` and `i64`. Only `char`, `i16` and `i32` are still sorted
+//! faster by the standard library.
 //!
 //! | type | quicksort | rdxsort | std |
 //! |-----:|----------:|--------:|----:|
-//! | `bool` | `4,070` | **`2,246`** | `26,068` |
-//! | `char` | `31,121` | **`20,204`** | `34,051` |
-//! | `f32` | `79,714` | **`25,825`** | `77,774` |
-//! | `f64` | `80,954` | **`52,262`** | `79,431` |
-//! | `i16` | `32,896` | **`12,496`** | `31,167` |
-//! | `i32` | `32,854` | **`22,009`** | `30,713` |
-//! | `i64` | `33,064` | `53,366` | **`31,669`** |
-//! | `i8` | `24,819` | **`8,190`** | `46,281` |
-//! | `u16` | `35,252` | **`9,937`** | `33,946` |
-//! | `u32` | `33,002` | **`19,202`** | `33,627` |
-//! | `u64` | **`32,986`** | `47,739` | `33,204` |
-//! | `u8` | `25,425` | **`5,170`** | `47,369` |
+//! | `bool` | `5,367` | **`4,388`** | `4,634` |
+//! | `char` | `29,124` | `21,832` | **`15,472`** |
+//! | `f32` | `32,739` | **`26,788`** | `30,697` |
+//! | `f64` | `45,025` | **`27,514`** | `31,508` |
+//! | `i128` | `56,459` | **`35,793`** | `36,154` |
+//! | `i16` | `33,761` | `24,294` | **`18,338`** |
+//! | `i32` | `32,780` | `21,440` | **`16,699`** |
+//! | `i64` | `28,614` | **`18,457`** | `20,291` |
+//! | `i8` | `20,590` | **`12,251`** | `15,605` |
+//! | `u128` | `30,290` | **`21,177`** | `33,370` |
+//! | `u16` | `28,263` | **`11,241`** | `12,050` |
+//! | `u32` | `23,891` | **`13,255`** | `20,093` |
+//! | `u64` | `23,363` | **`12,101`** | `16,777` |
+//! | `u8` | `19,372` | **`6,416`** | `15,317` |
 //!
 //!
 //! ### Medium (10'000 elements)
 //!
-//! For medium data sets Radix Sort could be blindly used for all data types since the disadvantage
-//! for types with 64 bits is quite small.
+//! For medium data sets Radix Sort is the best choice for data types with up to 32 bits size. For
+//! 64 bits and more, standard library sorting should be preferred.
 //!
 //! | type | quicksort | rdxsort | std |
 //! |-----:|----------:|--------:|----:|
-//! | `bool` | `52,211` | **`22,083`** | `400,111` |
-//! | `char` | `655,553` | **`192,328`** | `508,557` |
-//! | `f32` | `1,086,882` | **`230,670`** | `1,117,565` |
-//! | `f64` | `1,095,529` | **`417,104`** | `1,152,340` |
-//! | `i16` | `665,131` | **`108,128`** | `455,047` |
-//! | `i32` | `650,501` | **`202,533`** | `460,097` |
-//! | `i64` | `669,643` | **`378,480`** | `470,572` |
-//! | `i8` | `383,545` | **`65,521`** | `617,405` |
-//! | `u16` | `675,060` | **`78,424`** | `508,264` |
-//! | `u32` | `670,348` | **`177,068`** | `511,134` |
-//! | `u64` | `664,549` | **`342,935`** | `517,176` |
-//! | `u8` | `386,572` | **`37,012`** | `655,377` |
+//! | `bool` | `56,490` | `35,391` | **`26,352`** |
+//! | `char` | `926,825` | **`146,599`** | `282,361` |
+//! | `f32` | `947,042` | **`242,680`** | `454,167` |
+//! | `f64` | `1,183,454` | `552,892` | **`448,870`** |
+//! | `i128` | `1,204,683` | `680,714` | **`497,128`** |
+//! | `i16` | `1,003,630` | **`157,018`** | `303,720` |
+//! | `i32` | `1,077,508` | **`265,665`** | `293,846` |
+//! | `i64` | `1,122,434` | `554,303` | **`332,730`** |
+//! | `i8` | `602,124` | **`103,254`** | `143,865` |
+//! | `u128` | `1,342,405` | `742,502` | **`567,059`** |
+//! | `u16` | `1,106,095` | **`110,874`** | `299,891` |
+//! | `u32` | `1,052,095` | **`213,377`** | `286,692` |
+//! | `u64` | `1,055,171` | `480,438` | **`322,874`** |
+//! | `u8` | `622,617` | **`54,849`** | `132,146` |
 //!
 //!
 //! ### Large (100'000 elements)
 //!
-//! For large data sets, Radix Sort is great for all data types.
+//! For large data sets, Radix Sort is great for data types with up to 32 bits size. For 64 bits
+//! and more, standard library sorting is mostly faster.
 //!
 //! | type | quicksort | rdxsort | std |
 //! |-----:|----------:|--------:|----:|
-//! | `bool` | `815,653` | **`216,809`** | `4,900,426` |
-//! | `char` | `8,131,402` | **`2,538,064`** | `6,333,865` |
-//! | `f32` | `13,554,291` | **`3,264,657`** | `14,340,082` |
-//! | `f64` | `13,746,190` | **`7,122,334`** | `15,563,206` |
-//! | `i16` | `8,235,642` | **`1,248,289`** | `5,575,196` |
-//! | `i32` | `8,184,902` | **`2,494,882`** | `5,852,913` |
-//! | `i64` | `8,222,482` | **`5,446,507`** | `6,561,292` |
-//! | `i8` | `3,664,532` | **`703,288`** | `7,118,816` |
-//! | `u16` | `8,272,903` | **`866,833`** | `6,291,101` |
-//! | `u32` | `8,193,408` | **`2,051,413`** | `6,395,163` |
-//! | `u64` | `8,179,078` | **`4,393,579`** | `7,216,868` |
-//! | `u8` | `3,681,361` | **`367,240`** | `7,816,775` |
+//! | `bool` | `903,021` | **`424,699`** | `547,446` |
+//! | `char` | `12,178,592` | **`1,713,733`** | `3,679,023` |
+//! | `f32` | `16,351,309` | **`2,561,928`** | `5,942,341` |
+//! | `f64` | `14,592,227` | **`5,656,434`** | `6,139,686` |
+//! | `i128` | `14,957,159` | `11,272,394` | **`6,864,755`** |
+//! | `i16` | `11,960,078` | **`1,499,661`** | `3,481,019` |
+//! | `i32` | `12,482,025` | **`3,374,380`** | `4,235,670` |
+//! | `i64` | `12,204,213` | `4,248,584` | **`3,384,972`** |
+//! | `i8` | `4,532,089` | `884,992` | **`716,982`** |
+//! | `u128` | `13,975,251` | `9,546,996` | **`6,762,446`** |
+//! | `u16` | `13,193,368` | **`1,091,321`** | `3,147,749` |
+//! | `u32` | `12,845,729` | **`2,010,826`** | `3,497,752` |
+//! | `u64` | `11,794,706` | `4,479,679` | **`4,234,977`** |
+//! | `u8` | `5,719,024` | **`623,423`** | `769,449` |
 //!
 //!
 //! ## Implementing New Types
@@ -125,40 +201,38 @@
 //! This crate enables you to add support for new types by implementing `Rdx`. It
 //! describes how data is sorted into buckets and how many rounds of sorting are scheduled.
 //!
+//! For structs, and for enums whose variants have no fields, the implementation can also be
+//! derived using `#[derive(Rdx)]` when the `derive` feature is enabled. The resulting order
+//! matches the one of `#[derive(Ord)]`.
+//!
 //! ```
 //! use rdxsort::*;
 //!
-//! // `Clone` is required for `RdxSort`
 //! // `PartialEq` is only required for the equality assert, not for the actual sorting
-//! #[derive(Clone, PartialEq)]
+//! #[derive(PartialEq)]
 //! struct Foo {
 //!     a: u8,
 //!     b: u8,
 //! }
 //!
 //! impl Rdx for Foo {
-//!     // using `#[inline]` is generally recommended since it helps
-//!     // the compiler to optimize the sorting algorithm
-//!     #[inline]
-//!     fn cfg_nbuckets() -> usize {
-//!         // usually too high, but works as a simple demonstration
-//!         // `256 = 2^8`
-//!         256
-//!     }
+//!     // usually too high, but works as a simple demonstration
+//!     // `256 = 2^8`
+//!     const CFG_NBUCKETS: usize = 256;
 //!
-//!     #[inline]
-//!     fn cfg_nrounds() -> usize {
-//!         // one per sub-type
-//!         2
-//!     }
+//!     // one per sub-type
+//!     const CFG_NROUNDS: usize = 2;
 //!
+//!     // using `#[inline]` is generally recommended since it helps
+//!     // the compiler to optimize the sorting algorithm
 //!     #[inline]
-//!     fn get_bucket(&self, round: usize) -> usize {
-//!         // return the least significant digit first
+//!     fn get_bucket(&self, round: usize) -> Digit<Foo> {
+//!         // return the least significant digit first, `Digit::new` checks
+//!         // that it is in range (which is free if the compiler can prove it)
 //!         if round == 0 {
-//!             self.b as usize
+//!             Digit::new(self.b as usize)
 //!         } else {
-//!             self.a as usize
+//!             Digit::new(self.a as usize)
 //!         }
 //!     }
 //!
@@ -188,26 +262,38 @@
 //! }
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
 extern crate core;
 
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "derive")]
+extern crate rdxsort_derive;
+
 /// Generic Radix Sort implementation
 ///
 /// Works by splitting the work in rounds. During every round, the data is sorted into buckets and
-/// is then collected again. Rounds are count from `0` to (exclusive) `cfg_nrounds()`.
+/// is then collected again. Rounds are count from `0` to (exclusive) `CFG_NROUNDS`.
 ///
 /// The number of buckets is fixed for all rounds. That does not mean that the template has to use
 /// all of them, since unused buckets are just empty and have no effect on the collected results.
 /// Same hold for the number of rounds. Over-booking one or both of them wastes resources of
 /// course.
 ///
-/// **WARNING: The result returned from `get_bucket()` is not checked. Wrong implementations may
-/// crash the program, or destroy the world, or both!!!**
-pub trait Rdx {
+/// Buckets are returned as `Digit`, which can only be created for values below `CFG_NBUCKETS`.
+/// Implementations written for the old interface with `cfg_nbuckets()`, `cfg_nrounds()` and a
+/// plain `usize` bucket can be migrated using `rdx_legacy!`.
+pub trait Rdx: Sized {
     /// Sets the number of buckets used by the generic implementation.
-    fn cfg_nbuckets() -> usize;
+    const CFG_NBUCKETS: usize;
 
     /// Sets the number of rounds scheduled by the generic implementation.
-    fn cfg_nrounds() -> usize;
+    const CFG_NROUNDS: usize;
 
     /// Returns the bucket, depending on the round.
     ///
@@ -217,9 +303,7 @@ pub trait Rdx {
     ///   the bit starting with the least significant one.
     /// - if the number of buckets is `8` and the type is an unsigned integer, then the result is
     ///   the byte starting with the least significant one.
-    ///
-    /// **Never** return a bucker greater or equal the number of buckets. See warning above!
-    fn get_bucket(&self, round: usize) -> usize;
+    fn get_bucket(&self, round: usize) -> Digit<Self>;
 
     /// Describes the fact that the content of a bucket should be copied back in reverse order
     /// after a certain round.
@@ -231,22 +315,15 @@ pub trait Rdx {
 macro_rules! rdx_alias {
     ($t1:ty = $t2:ty) => {
         impl Rdx for $t1 {
-            #[inline]
-            fn cfg_nbuckets() -> usize {
-                <$t2 as Rdx>::cfg_nbuckets()
-            }
+            const CFG_NBUCKETS: usize = <$t2 as Rdx>::CFG_NBUCKETS;
 
-            #[inline]
-            fn cfg_nrounds() -> usize {
-                <$t2 as Rdx>::cfg_nrounds()
-            }
+            const CFG_NROUNDS: usize = <$t2 as Rdx>::CFG_NROUNDS;
 
             #[inline]
-            fn get_bucket(&self, round: usize) -> usize {
-                (*self as $t2).get_bucket(round)
+            fn get_bucket(&self, round: usize) -> $crate::Digit<$t1> {
+                (*self as $t2).get_bucket(round).cast()
             }
 
-
             #[inline]
             fn reverse(round: usize, bucket: usize) -> bool {
                 <$t2 as Rdx>::reverse(round, bucket)
@@ -255,9 +332,103 @@ macro_rules! rdx_alias {
     }
 }
 
+/// Migration shim for `Rdx` implementations that were written for the old interface, where
+/// `cfg_nbuckets()` and `cfg_nrounds()` were functions and `get_bucket()` returned a plain `usize`.
+///
+/// The old implementation block can be wrapped as it is. The bodies of `cfg_nbuckets()` and
+/// `cfg_nrounds()` have to be constant expressions, so calls like `u8::cfg_nbuckets()` must be
+/// replaced by `u8::CFG_NBUCKETS`. Buckets returned by `get_bucket()` are checked at runtime.
+///
+/// ```
+/// #[macro_use]
+/// extern crate rdxsort;
+///
+/// use rdxsort::*;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct Foo(u8);
+///
+/// rdx_legacy! {
+///     impl Rdx for Foo {
+///         #[inline]
+///         fn cfg_nbuckets() -> usize {
+///             16
+///         }
+///
+///         #[inline]
+///         fn cfg_nrounds() -> usize {
+///             2
+///         }
+///
+///         #[inline]
+///         fn get_bucket(&self, round: usize) -> usize {
+///             ((self.0 >> (round * 4)) & 15) as usize
+///         }
+///
+///         #[inline]
+///         fn reverse(_round: usize, _bucket: usize) -> bool {
+///             false
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let mut data = vec![Foo(3), Foo(200), Foo(1)];
+///     data.rdxsort();
+///     assert!(data == vec![Foo(1), Foo(3), Foo(200)]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! rdx_legacy {
+    (
+        impl Rdx for $t:ty {
+            $(#[$m1:meta])*
+            fn cfg_nbuckets() -> usize $nbuckets:block
+
+            $(#[$m2:meta])*
+            fn cfg_nrounds() -> usize $nrounds:block
+
+            $(#[$m3:meta])*
+            fn get_bucket(&$slf:ident, $round:ident: usize) -> usize $get_bucket:block
+
+            $(#[$m4:meta])*
+            fn reverse($round2:ident: usize, $bucket:ident: usize) -> bool $reverse:block
+        }
+    ) => {
+        impl Rdx for $t {
+            const CFG_NBUCKETS: usize = $nbuckets;
+
+            const CFG_NROUNDS: usize = $nrounds;
+
+            $(#[$m3])*
+            fn get_bucket(&$slf, $round: usize) -> $crate::Digit<$t> {
+                $crate::Digit::new($get_bucket)
+            }
+
+            $(#[$m4])*
+            fn reverse($round2: usize, $bucket: usize) -> bool $reverse
+        }
+    }
+}
+
+mod digit;
+mod map;
+mod msd;
+#[cfg(feature = "rayon")]
+mod par;
 mod sort;
+mod sorter;
 mod tree;
 mod types;
 
-pub use sort::RdxSort;
-pub use tree::{RdxTree, RdxTreeIter};
+#[cfg(feature = "rayon")]
+pub use par::RdxParSort;
+#[cfg(feature = "derive")]
+pub use rdxsort_derive::Rdx;
+pub use digit::Digit;
+pub use map::{Entry, OccupiedEntry, RdxMap, RdxMapIntoIter, RdxMapIter, RdxMapKeys, RdxMapRange,
+              RdxMapValues, VacantEntry};
+pub use sort::{RdxArgSort, RdxPermute, RdxSort, RdxSortByKey, RdxSortWith};
+pub use sorter::RdxSorter;
+pub use tree::{RdxTree, RdxTreeIntoIter, RdxTreeIter, RdxTreeRange};
+pub use types::{NanLast, Radix8, Radix11, Radix16, TotalOrd};
@@ -0,0 +1,798 @@
+use super::Rdx;
+use super::tree::{Node, NodeInner, NodeLimited, NodePruned};
+
+use alloc::vec::{self, Vec};
+
+use core::cmp::Ordering;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use core::ptr;
+
+/// Ordered map from `Rdx` keys to values, based on a radix tree.
+///
+/// Keys are ordered like `RdxSort` would sort them. Two keys are considered equal if they end up
+/// in the same bucket for all rounds.
+///
+/// Like `RdxTree`, the map is `Send` and `Sync` if `K` and `V` are.
+pub struct RdxMap<K, V>
+    where K: Rdx
+{
+    root: NodeInner<K, V>,
+    len: usize,
+}
+
+
+impl<K, V> RdxMap<K, V>
+    where K: Rdx
+{
+    pub fn new() -> RdxMap<K, V> {
+        RdxMap {
+            root: NodeInner::new(K::CFG_NROUNDS),
+            len: 0,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn root(&self) -> &NodeInner<K, V> {
+        &self.root
+    }
+
+    /// Inserts `k` with value `v` and returns the value of the equal key that was present
+    /// before, if any. Like for `BTreeMap`, the stored key is not replaced in that case.
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        let replaced = self.root.insert(k, v).1;
+        if replaced.is_none() {
+            self.len += 1;
+        }
+        replaced
+    }
+
+    /// Returns `true` if the map contains a key that is equal to `k`.
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.root.find(k).is_some()
+    }
+
+    /// Returns the value of the key that is equal to `k`.
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.root.find(k).map(|(_, v)| v)
+    }
+
+    /// Returns the stored key that is equal to `k` and its value.
+    pub fn get_key_value(&self, k: &K) -> Option<(&K, &V)> {
+        self.root.find(k)
+    }
+
+    /// Returns a mutable reference to the value of the key that is equal to `k`.
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        self.root.find_mut(k).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the stored key that is equal to `k`, which is used by
+    /// `RdxTree::replace`. The key must only be replaced by an equal one.
+    pub(crate) fn get_key_mut(&mut self, k: &K) -> Option<&mut K> {
+        self.root.find_mut(k).map(|(k, _)| k)
+    }
+
+    /// Removes the key that is equal to `k` and returns its value.
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes the key that is equal to `k` and returns it together with its value.
+    pub fn remove_entry(&mut self, k: &K) -> Option<(K, V)> {
+        let taken = self.root.take(k);
+        if taken.is_some() {
+            self.len -= 1;
+        }
+        taken
+    }
+
+    /// Returns the entry of `k` for in-place manipulation.
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V> {
+        if self.contains_key(&k) {
+            Entry::Occupied(OccupiedEntry { key: k, map: self })
+        } else {
+            Entry::Vacant(VacantEntry { key: k, map: self })
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map does not contain any element.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        *self = RdxMap::new();
+    }
+
+    /// Returns an iterator over all keys and values, ordered by key.
+    pub fn iter(&self) -> RdxMapIter<'_, K, V> {
+        RdxMapIter {
+            front: Cursor::new(&self.root, false),
+            back: Cursor::new(&self.root, true),
+            len: self.len,
+        }
+    }
+
+    /// Returns an iterator over the keys within `range` and their values, ordered by key.
+    ///
+    /// The bounds do not need to be present in the map. The iterator is empty if the start of the
+    /// range lies behind its end.
+    pub fn range<R>(&self, range: R) -> RdxMapRange<'_, K, V>
+        where R: RangeBounds<K>
+    {
+        RdxMapRange::new(&self.root, range.start_bound(), range.end_bound())
+    }
+
+    /// Returns the smallest key and its value.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        Cursor::new(&self.root, false).next()
+    }
+
+    /// Returns the largest key and its value.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        Cursor::new(&self.root, true).next()
+    }
+
+    /// Returns the largest key that is less than or equal to `k` and its value.
+    pub fn floor_key_value(&self, k: &K) -> Option<(&K, &V)> {
+        Cursor::seek(&self.root, Bound::Included(k), true).next()
+    }
+
+    /// Returns the smallest key that is greater than or equal to `k` and its value.
+    pub fn ceiling_key_value(&self, k: &K) -> Option<(&K, &V)> {
+        Cursor::seek(&self.root, Bound::Included(k), false).next()
+    }
+
+    /// Returns an iterator over all keys in order.
+    pub fn keys(&self) -> RdxMapKeys<'_, K, V> {
+        RdxMapKeys { iter: self.iter() }
+    }
+
+    /// Returns an iterator over all values, ordered by key.
+    pub fn values(&self) -> RdxMapValues<'_, K, V> {
+        RdxMapValues { iter: self.iter() }
+    }
+
+    pub fn nnodes(&self) -> (usize, usize, usize, usize) {
+        self.root.nnodes()
+    }
+}
+
+
+impl<K, V> Default for RdxMap<K, V>
+    where K: Rdx
+{
+    fn default() -> RdxMap<K, V> {
+        RdxMap::new()
+    }
+}
+
+
+/// View of a single key of a `RdxMap`, see `RdxMap::entry`.
+pub enum Entry<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+
+impl<'a, K, V> Entry<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    /// Inserts `v` if the entry is vacant and returns a mutable reference to the value.
+    pub fn or_insert(self, v: V) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => entry.insert(v),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Inserts the result of `f` if the entry is vacant and returns a mutable reference to the
+    /// value.
+    pub fn or_insert_with<F>(self, f: F) -> &'a mut V
+        where F: FnOnce() -> V
+    {
+        match self {
+            Entry::Vacant(entry) => entry.insert(f()),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Inserts the default value if the entry is vacant and returns a mutable reference to the
+    /// value.
+    pub fn or_default(self) -> &'a mut V
+        where V: Default
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    pub fn and_modify<F>(self, f: F) -> Entry<'a, K, V>
+        where F: FnOnce(&mut V)
+    {
+        match self {
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+        }
+    }
+
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Vacant(ref entry) => entry.key(),
+            Entry::Occupied(ref entry) => entry.key(),
+        }
+    }
+}
+
+
+/// Entry of a key that is not present in the map.
+pub struct VacantEntry<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    key: K,
+    map: &'a mut RdxMap<K, V>,
+}
+
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    /// Returns the key that would be inserted.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the key without inserting it.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the key with value `v` and returns a mutable reference to the value.
+    pub fn insert(self, v: V) -> &'a mut V {
+        let map = self.map;
+        map.len += 1;
+        map.root.insert(self.key, v).0
+    }
+}
+
+
+/// Entry of a key that is present in the map.
+pub struct OccupiedEntry<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    // key that was used for the lookup, not the stored one
+    key: K,
+    map: &'a mut RdxMap<K, V>,
+}
+
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    /// Returns the stored key.
+    pub fn key(&self) -> &K {
+        self.map.root.find(&self.key).expect("bug: entry is not occupied").0
+    }
+
+    /// Returns the value.
+    pub fn get(&self) -> &V {
+        self.map.root.find(&self.key).expect("bug: entry is not occupied").1
+    }
+
+    /// Returns a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.map.root.find_mut(&self.key).expect("bug: entry is not occupied").1
+    }
+
+    /// Returns a mutable reference to the value that lives as long as the map borrow.
+    pub fn into_mut(self) -> &'a mut V {
+        self.map.root.find_mut(&self.key).expect("bug: entry is not occupied").1
+    }
+
+    /// Replaces the value and returns the old one.
+    pub fn insert(&mut self, v: V) -> V {
+        mem::replace(self.get_mut(), v)
+    }
+
+    /// Removes the entry from the map and returns the value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes the entry from the map and returns the stored key and the value.
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_entry(&self.key).expect("bug: entry is not occupied")
+    }
+}
+
+
+/// Position within a `RdxMap`, walks the tree in either direction.
+struct Cursor<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    // iterator stack:
+    //   - inner node
+    //   - number of children that were visited already
+    //   - reverse the iterator order for this subpart if `true`
+    stack: Vec<(&'a NodeInner<K, V>, usize, bool)>,
+}
+
+
+impl<'a, K, V> Cursor<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    /// Creates a cursor that visits all elements, in descending order if `back` is `true`.
+    fn new(root: &'a NodeInner<K, V>, back: bool) -> Cursor<'a, K, V> {
+        Cursor { stack: vec![(root, 0, back)] }
+    }
+
+    /// Creates a cursor that starts at `bound`, in descending order if `back` is `true`.
+    ///
+    /// Only the digit path of the bound is followed, the stack is set up so that all buckets
+    /// before the path are already visited.
+    fn seek(root: &'a NodeInner<K, V>, bound: Bound<&K>, back: bool) -> Cursor<'a, K, V> {
+        let (k, inclusive) = match bound {
+            Bound::Included(k) => (k, true),
+            Bound::Excluded(k) => (k, false),
+            Bound::Unbounded => return Cursor::new(root, back),
+        };
+
+        let mut stack = Vec::new();
+        let mut inner = root;
+        let mut rev = back;
+        loop {
+            let n = inner.children.len();
+            let bucket = k.get_bucket(inner.round - 1).get();
+            let pos = if rev { n - 1 - bucket } else { bucket };
+            let rev_child = rev ^ K::reverse(inner.round - 1, bucket);
+
+            // decide if the bucket has to be visited and if the path continues below it
+            let (skip, next) = match inner.children[bucket] {
+                Node::Free => (true, None),
+                Node::Child(..) => (!inclusive, None),
+                Node::Inner(ref child) => (true, Some((&**child, rev_child))),
+                Node::Pruned(ref pruned) => {
+                    match locate(pruned, k, rev_child) {
+                        (Ordering::Less, _) => (false, None),
+                        (Ordering::Greater, _) => (true, None),
+                        (Ordering::Equal, rev_end) => {
+                            match pruned.child {
+                                NodeLimited::Child(..) => (!inclusive, None),
+                                NodeLimited::Inner(ref child) => {
+                                    (true, Some((&**child, rev_end)))
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            stack.push((inner, if skip { pos + 1 } else { pos }, rev));
+
+            match next {
+                Some((child, rev_child)) => {
+                    inner = child;
+                    rev = rev_child;
+                }
+                None => return Cursor { stack },
+            }
+        }
+    }
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        // the iteration is basically the processing of a stack machine
+        loop {
+            // pick the next child of the node on top of the stack, or pop that node when all of
+            // its children were visited
+            let state = self.stack.last_mut()?;
+            let (inner, rev) = (state.0, state.2);
+            let n = inner.children.len();
+            let next = if state.1 < n {
+                let bucket = if rev { n - 1 - state.1 } else { state.1 };
+                state.1 += 1;
+                Some((inner, bucket, rev))
+            } else {
+                None
+            };
+            let (inner, bucket, rev) = match next {
+                Some(x) => x,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            // the content of the bucket might be reversed
+            let rev = rev ^ K::reverse(inner.round - 1, bucket);
+            match inner.children[bucket] {
+                Node::Free => {}
+                Node::Child(ref k, ref v) => {
+                    return Some((k, v));
+                }
+                Node::Inner(ref child) => {
+                    self.stack.push((child, 0, rev));
+                }
+                Node::Pruned(ref pruned) => {
+                    match pruned.child {
+                        NodeLimited::Child(ref k, ref v) => {
+                            return Some((k, v));
+                        }
+                        NodeLimited::Inner(ref child) => {
+                            // simulate traversal of pruned tree part to recover `reverse`
+                            let rev = pruned.buckets
+                                .iter()
+                                .enumerate()
+                                .fold(rev, |rev, (i, &b)| {
+                                    rev ^ K::reverse(pruned.round - 1 - i, b)
+                                });
+                            self.stack.push((child, 0, rev));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+/// Compares `k` to the pruned path in iteration order, where `rev` is the iteration order at the
+/// start of the path. Also returns the iteration order at the end of the path.
+fn locate<K, V>(pruned: &NodePruned<K, V>, k: &K, rev: bool) -> (Ordering, bool)
+    where K: Rdx
+{
+    let mut rev = rev;
+    for (i, &bucket) in pruned.buckets.iter().enumerate() {
+        let round = pruned.round - 1 - i;
+        let b = k.get_bucket(round).get();
+        if b != bucket {
+            let ord = b.cmp(&bucket);
+            return (if rev { ord.reverse() } else { ord }, rev);
+        }
+        rev ^= K::reverse(round, b);
+    }
+    (Ordering::Equal, rev)
+}
+
+
+/// Compares two keys the same way `RdxSort` orders them.
+fn cmp_keys<K>(a: &K, b: &K) -> Ordering
+    where K: Rdx
+{
+    let mut rev = false;
+    for round in (0..K::CFG_NROUNDS).rev() {
+        let bucket_a = a.get_bucket(round).get();
+        let bucket_b = b.get_bucket(round).get();
+        if bucket_a != bucket_b {
+            let ord = bucket_a.cmp(&bucket_b);
+            return if rev { ord.reverse() } else { ord };
+        }
+        rev ^= K::reverse(round, bucket_a);
+    }
+    Ordering::Equal
+}
+
+
+/// Iterator over the keys and values of a `RdxMap`, see `RdxMap::iter`.
+pub struct RdxMapIter<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    front: Cursor<'a, K, V>,
+    back: Cursor<'a, K, V>,
+
+    // number of elements that were not yielded by either end yet
+    len: usize,
+}
+
+
+impl<'a, K, V> Iterator for RdxMapIter<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+
+impl<'a, K, V> DoubleEndedIterator for RdxMapIter<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.back.next()
+    }
+}
+
+
+impl<'a, K, V> ExactSizeIterator for RdxMapIter<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+}
+
+
+/// Iterator over a range of keys and their values of a `RdxMap`, see `RdxMap::range`.
+pub struct RdxMapRange<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    front: Cursor<'a, K, V>,
+    back: Cursor<'a, K, V>,
+
+    // next elements of both ends, both are `None` once they met
+    next_front: Option<(&'a K, &'a V)>,
+    next_back: Option<(&'a K, &'a V)>,
+}
+
+
+impl<'a, K, V> RdxMapRange<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    fn new(root: &'a NodeInner<K, V>, start: Bound<&K>, end: Bound<&K>) -> RdxMapRange<'a, K, V> {
+        let mut front = Cursor::seek(root, start, false);
+        let mut back = Cursor::seek(root, end, true);
+        let (next_front, next_back) = match (front.next(), back.next()) {
+            (Some(a), Some(b)) if cmp_keys(a.0, b.0) != Ordering::Greater => (Some(a), Some(b)),
+            _ => (None, None),
+        };
+        RdxMapRange {
+            front,
+            back,
+            next_front,
+            next_back,
+        }
+    }
+
+    /// Returns `true` if both ends point to the same element, i.e. only one element is left.
+    fn is_last(&self) -> bool {
+        match (self.next_front, self.next_back) {
+            (Some(a), Some(b)) => ptr::eq(a.0, b.0),
+            _ => true,
+        }
+    }
+}
+
+
+impl<'a, K, V> Iterator for RdxMapRange<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.next_front?;
+        if self.is_last() {
+            self.next_front = None;
+            self.next_back = None;
+        } else {
+            self.next_front = self.front.next();
+        }
+        Some(x)
+    }
+}
+
+
+impl<'a, K, V> DoubleEndedIterator for RdxMapRange<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let x = self.next_back?;
+        if self.is_last() {
+            self.next_front = None;
+            self.next_back = None;
+        } else {
+            self.next_back = self.back.next();
+        }
+        Some(x)
+    }
+}
+
+
+/// Iterator over the keys of a `RdxMap`, see `RdxMap::keys`.
+pub struct RdxMapKeys<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    iter: RdxMapIter<'a, K, V>,
+}
+
+
+impl<'a, K, V> Iterator for RdxMapKeys<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<'a, K, V> DoubleEndedIterator for RdxMapKeys<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, _)| k)
+    }
+}
+
+
+impl<'a, K, V> ExactSizeIterator for RdxMapKeys<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+}
+
+
+/// Iterator over the values of a `RdxMap`, see `RdxMap::values`.
+pub struct RdxMapValues<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    iter: RdxMapIter<'a, K, V>,
+}
+
+
+impl<'a, K, V> Iterator for RdxMapValues<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<'a, K, V> DoubleEndedIterator for RdxMapValues<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, v)| v)
+    }
+}
+
+
+impl<'a, K, V> ExactSizeIterator for RdxMapValues<'a, K, V>
+    where K: Rdx + 'a,
+          V: 'a
+{
+}
+
+
+/// Owning iterator over the keys and values of a `RdxMap`, see `RdxMap::into_iter`.
+pub struct RdxMapIntoIter<K, V> {
+    iter: vec::IntoIter<(K, V)>,
+}
+
+
+impl<K, V> Iterator for RdxMapIntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<K, V> DoubleEndedIterator for RdxMapIntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+
+impl<K, V> ExactSizeIterator for RdxMapIntoIter<K, V> {}
+
+
+/// Moves all elements of the subtree into `out`, in iteration order.
+fn drain_into<K, V>(inner: NodeInner<K, V>, rev: bool, out: &mut Vec<(K, V)>)
+    where K: Rdx
+{
+    let round = inner.round;
+    let mut children: Vec<(usize, Node<K, V>)> = inner.children.into_iter().enumerate().collect();
+    if rev {
+        children.reverse();
+    }
+
+    for (bucket, child) in children {
+        // the content of the bucket might be reversed
+        let rev = rev ^ K::reverse(round - 1, bucket);
+        match child {
+            Node::Free => {}
+            Node::Child(k, v) => out.push((k, v)),
+            Node::Inner(child) => drain_into(*child, rev, out),
+            Node::Pruned(pruned) => {
+                let NodePruned { round, buckets, child } = *pruned;
+                match child {
+                    NodeLimited::Child(k, v) => out.push((k, v)),
+                    NodeLimited::Inner(child) => {
+                        let rev = buckets.iter()
+                            .enumerate()
+                            .fold(rev, |rev, (i, &b)| rev ^ K::reverse(round - 1 - i, b));
+                        drain_into(*child, rev, out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+impl<K, V> IntoIterator for RdxMap<K, V>
+    where K: Rdx
+{
+    type Item = (K, V);
+    type IntoIter = RdxMapIntoIter<K, V>;
+
+    /// Moves all keys and values out of the map, ordered by key.
+    fn into_iter(self) -> RdxMapIntoIter<K, V> {
+        let mut out = Vec::with_capacity(self.len);
+        drain_into(self.root, false, &mut out);
+        RdxMapIntoIter { iter: out.into_iter() }
+    }
+}
+
+
+impl<'a, K, V> IntoIterator for &'a RdxMap<K, V>
+    where K: Rdx
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = RdxMapIter<'a, K, V>;
+
+    fn into_iter(self) -> RdxMapIter<'a, K, V> {
+        self.iter()
+    }
+}
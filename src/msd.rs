@@ -0,0 +1,561 @@
+use super::{Digit, Rdx};
+use super::sort::helper_permute;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// Ranges up to this size are sorted using insertion sort instead of another radix round.
+const CFG_INSERTION_THRESHOLD: usize = 16;
+
+/// In-place MSD Radix Sort (a.k.a. American Flag Sort) of `data` using the rounds and buckets of
+/// `K`, where `get_bucket` returns the bucket of an element for a given round.
+///
+/// The sort is unstable and requires `O(nbuckets * nrounds)` extra memory.
+pub fn rdxsort_unstable_template<T, K, F>(data: &mut [T], get_bucket: F)
+    where K: Rdx,
+          F: Fn(&T, usize) -> Digit<K>
+{
+    // config
+    let cfg_nbuckets = K::CFG_NBUCKETS;
+    let cfg_nrounds = K::CFG_NROUNDS;
+
+    // early return
+    if cfg_nrounds == 0 || data.len() < 2 {
+        return;
+    }
+
+    let sorter = Sorter {
+        cfg_nbuckets,
+        get_bucket,
+        phantom: PhantomData::<(*const T, K)>,
+    };
+
+    // bucket boundaries and heads, one set per round
+    let mut bounds = vec![0; (2 * cfg_nbuckets + 1) * cfg_nrounds];
+    sorter.sort(data, cfg_nrounds - 1, false, &mut bounds);
+}
+
+struct Sorter<T, K, F>
+    where K: Rdx,
+          F: Fn(&T, usize) -> Digit<K>
+{
+    cfg_nbuckets: usize,
+    get_bucket: F,
+    phantom: PhantomData<(*const T, K)>,
+}
+
+impl<T, K, F> Sorter<T, K, F>
+    where K: Rdx,
+          F: Fn(&T, usize) -> Digit<K>
+{
+    #[inline]
+    fn bucket(&self, x: &T, round: usize) -> usize {
+        (self.get_bucket)(x, round).get()
+    }
+
+    /// Sorts `data` by the rounds `0..(round + 1)`, starting with the most significant one. If
+    /// `rev` is set, the order of all buckets gets inverted, e.g. because a previous round asked
+    /// for reversion.
+    fn sort(&self, data: &mut [T], round: usize, rev: bool, bounds: &mut [usize]) {
+        if data.len() <= CFG_INSERTION_THRESHOLD {
+            self.insertion_sort(data, round, rev);
+            return;
+        }
+
+        let (bounds, bounds_sub) = bounds.split_at_mut(2 * self.cfg_nbuckets + 1);
+        let (bounds, heads) = bounds.split_at_mut(self.cfg_nbuckets + 1);
+
+        // histogram
+        for b in bounds.iter_mut() {
+            *b = 0;
+        }
+        for x in data.iter() {
+            bounds[self.bucket(x, round)] += 1;
+        }
+
+        // the bucket is trivial, so skip the permutation
+        if bounds.contains(&data.len()) {
+            let b = self.bucket(&data[0], round);
+            if round > 0 {
+                self.sort(data, round - 1, rev ^ K::reverse(round, b), bounds_sub);
+            }
+            return;
+        }
+
+        // convert counts to bucket starts, respecting the (maybe reversed) bucket order
+        let mut sum = 0;
+        for i in 0..self.cfg_nbuckets {
+            let b = if rev { self.cfg_nbuckets - 1 - i } else { i };
+            let count = bounds[b];
+            bounds[b] = sum;
+            sum += count;
+        }
+        bounds[self.cfg_nbuckets] = sum;
+
+        // permutation, `heads` tracks the next unsorted position of every bucket
+        heads.copy_from_slice(&bounds[..self.cfg_nbuckets]);
+        for i in 0..self.cfg_nbuckets {
+            let b = if rev { self.cfg_nbuckets - 1 - i } else { i };
+            let end = self.bucket_end(bounds, b, rev);
+            while heads[b] < end {
+                let target = self.bucket(&data[heads[b]], round);
+                if target == b {
+                    heads[b] += 1;
+                } else {
+                    data.swap(heads[b], heads[target]);
+                    heads[target] += 1;
+                }
+            }
+        }
+
+        // sort buckets by the remaining rounds
+        if round > 0 {
+            for b in 0..self.cfg_nbuckets {
+                let start = bounds[b];
+                let end = self.bucket_end(bounds, b, rev);
+                if end - start > 1 {
+                    self.sort(&mut data[start..end],
+                              round - 1,
+                              rev ^ K::reverse(round, b),
+                              bounds_sub);
+                }
+            }
+        }
+    }
+
+    /// Returns the (exclusive) end of bucket `b`, which is the start of the next bucket.
+    #[inline]
+    fn bucket_end(&self, bounds: &[usize], b: usize, rev: bool) -> usize {
+        if rev {
+            if b == 0 {
+                bounds[self.cfg_nbuckets]
+            } else {
+                bounds[b - 1]
+            }
+        } else {
+            bounds[b + 1]
+        }
+    }
+
+    /// Compares two elements by the rounds `0..(round + 1)`.
+    fn less(&self, x: &T, y: &T, round: usize, rev: bool) -> bool {
+        let mut rev = rev;
+        for r in (0..(round + 1)).rev() {
+            let bx = self.bucket(x, r);
+            let by = self.bucket(y, r);
+            if bx != by {
+                return (bx < by) != rev;
+            }
+            rev ^= K::reverse(r, bx);
+        }
+        false
+    }
+
+    fn insertion_sort(&self, data: &mut [T], round: usize, rev: bool) {
+        for i in 1..data.len() {
+            let mut j = i;
+            while j > 0 && self.less(&data[j], &data[j - 1], round, rev) {
+                data.swap(j, j - 1);
+                j -= 1;
+            }
+        }
+    }
+}
+
+/// Upper limit for the number of buckets of a pass of the stable MSD variant.
+const CFG_STABLE_NBUCKETS: usize = 256;
+
+/// Returns the number of `buffer` entries `rdxsort_stable_template` needs for `n` elements.
+#[inline]
+pub fn helper_stable_buffer_len<K>(n: usize, cfg_nrounds: usize) -> usize
+    where K: Rdx
+{
+    // the digits of all elements, the buckets of one pass and the bounds of the large buckets of
+    // all passes that are in progress
+    n + 3 * CFG_STABLE_NBUCKETS.max(K::CFG_NBUCKETS) + 1 +
+    2 * (n / CFG_INSERTION_THRESHOLD + cfg_nrounds)
+}
+
+/// Stable MSD Radix Sort of `data` using the rounds `0..cfg_nrounds` of `K`, where `get_bucket`
+/// returns the bucket of an element for a given round. The result is the same as the one of the
+/// LSD implementation, including the order of equal elements and of reversed buckets.
+///
+/// For small data sets this is faster than LSD, since most buckets are small after a single pass
+/// and are finished by insertion sort instead of going through all remaining rounds. To make the
+/// most of that pass, its digits combine up to three rounds as long as there are enough elements
+/// to fill the buckets.
+///
+/// `scratch` must have the same length as `data` and `buffer` must hold
+/// `helper_stable_buffer_len::<K>(data.len(), cfg_nrounds)` entries, the content of both is
+/// overwritten.
+pub fn rdxsort_stable_template<T, K, F>(data: &mut [T],
+                                        scratch: &mut [MaybeUninit<T>],
+                                        buffer: &mut [usize],
+                                        cfg_nrounds: usize,
+                                        get_bucket: F)
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    // config
+    let n = data.len();
+    assert!(scratch.len() == n, "scratch length does not match the data length");
+    assert!(buffer.len() == helper_stable_buffer_len::<K>(n, cfg_nrounds),
+            "bug: wrong number of buffer entries");
+
+    // early return
+    if cfg_nrounds == 0 || n < 2 {
+        return;
+    }
+
+    let mut sorter = StableSorter {
+        get_bucket,
+        phantom: PhantomData::<(*const T, K)>,
+    };
+    let (digits, bounds) = buffer.split_at_mut(n);
+    sorter.sort(data, scratch, digits, cfg_nrounds - 1, bounds);
+}
+
+struct StableSorter<T, K, F>
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    get_bucket: F,
+    phantom: PhantomData<(*const T, K)>,
+}
+
+impl<T, K, F> StableSorter<T, K, F>
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    #[inline]
+    fn bucket(&mut self, x: &T, round: usize) -> usize {
+        (self.get_bucket)(x, round).get()
+    }
+
+    /// Returns the bucket of round `round`, combined with the one of `round - 1` if `two` is set.
+    /// The order of the combined digits respects the reversion of the higher round.
+    #[inline]
+    fn digit(&mut self, x: &T, round: usize, two: bool) -> usize {
+        let cfg_nbuckets = K::CFG_NBUCKETS;
+        let b = self.bucket(x, round);
+        if two {
+            let b_low = self.bucket(x, round - 1);
+            let b_low = if K::reverse(round, b) { cfg_nbuckets - 1 - b_low } else { b_low };
+            b * cfg_nbuckets + b_low
+        } else {
+            b
+        }
+    }
+
+    /// Returns `true` if the elements that share the buckets of `x` in the rounds
+    /// `low..(round + 1)` have to be reversed after they were sorted by the lower rounds.
+    #[inline]
+    fn reversed(&mut self, x: &T, round: usize, low: usize) -> bool {
+        let mut rev = false;
+        for r in low..(round + 1) {
+            let b = self.bucket(x, r);
+            rev ^= K::reverse(r, b);
+        }
+        rev
+    }
+
+    /// Sorts `data` by the rounds `0..(round + 1)`, starting with the most significant ones. Every
+    /// bucket keeps the input order of its elements until it is sorted itself, reversed buckets
+    /// get inverted afterwards.
+    fn sort(&mut self,
+            data: &mut [T],
+            scratch: &mut [MaybeUninit<T>],
+            digits: &mut [usize],
+            round: usize,
+            bounds: &mut [usize]) {
+        let n = data.len();
+        if n <= CFG_INSERTION_THRESHOLD {
+            self.insertion_sort(data, round);
+            return;
+        }
+
+        // combine two rounds if there are enough elements to fill the buckets
+        let cfg_nbuckets = K::CFG_NBUCKETS;
+        let limit = CFG_STABLE_NBUCKETS.min(n);
+        let two = round > 0 && cfg_nbuckets <= limit / cfg_nbuckets;
+        let mut nbuckets = if two { cfg_nbuckets * cfg_nbuckets } else { cfg_nbuckets };
+        let mut low = if two { round - 1 } else { round };
+
+        // histogram, the digits are kept so that every element only gets bucketed once
+        let (counts, rest) = bounds.split_at_mut(nbuckets);
+        for count in counts.iter_mut() {
+            *count = 0;
+        }
+        for (x, digit) in data.iter().zip(digits.iter_mut()) {
+            *digit = self.digit(x, round, two);
+            counts[*digit] += 1;
+        }
+
+        // the bucket is trivial, so skip the scatter but still respect a possible reversion
+        if counts.contains(&n) {
+            let rev = self.reversed(&data[0], round, low);
+            if low > 0 {
+                self.sort(data, scratch, digits, low - 1, bounds);
+            }
+            if rev {
+                data.reverse();
+            }
+            return;
+        }
+
+        // if only few digits are used, e.g. because the highest round only holds the sign, they
+        // get ranked and the next round is appended to them
+        let used = counts.iter().filter(|&&count| count > 0).count();
+        let refine = two && low > 0 && used <= limit / cfg_nbuckets;
+        let ncounts = nbuckets;
+        if refine {
+            nbuckets = used * cfg_nbuckets;
+        }
+        let (starts, rest) = rest.split_at_mut(nbuckets + 1);
+        let offsets = &mut rest[..nbuckets];
+
+        // bucket starts, shifted by one during counting
+        starts[0] = 0;
+        if refine {
+            // store the rank and the reversion of every used digit
+            let mut rank = 0;
+            for (digit, count) in counts.iter_mut().enumerate() {
+                if *count > 0 {
+                    let (b, b_low) = (digit / cfg_nbuckets, digit % cfg_nbuckets);
+                    let rev = K::reverse(round, b);
+                    let b_low = if rev { cfg_nbuckets - 1 - b_low } else { b_low };
+                    *count = 2 * rank + (rev != K::reverse(round - 1, b_low)) as usize;
+                    rank += 1;
+                }
+            }
+
+            low -= 1;
+            for count in starts[1..].iter_mut() {
+                *count = 0;
+            }
+            for (x, digit) in data.iter().zip(digits.iter_mut()) {
+                let entry = counts[*digit];
+                let b = self.bucket(x, low);
+                let b = if entry % 2 == 1 { cfg_nbuckets - 1 - b } else { b };
+                *digit = entry / 2 * cfg_nbuckets + b;
+                starts[*digit + 1] += 1;
+            }
+        } else {
+            starts[1..].copy_from_slice(counts);
+        }
+        for b in 0..nbuckets {
+            starts[b + 1] += starts[b];
+        }
+        offsets.copy_from_slice(&starts[..nbuckets]);
+
+        // the elements are copied bitwise, so `data` stays valid until they are moved back
+        for (x, &digit) in data.iter().zip(digits.iter()) {
+            let pos = &mut offsets[digit];
+            scratch[*pos] = MaybeUninit::new(unsafe { ptr::read(x) });
+            *pos += 1;
+        }
+        assert!(*offsets == starts[1..], "bug: bucket size does not sum up");
+        unsafe {
+            ptr::copy_nonoverlapping(scratch.as_ptr() as *const T, data.as_mut_ptr(), n);
+        }
+
+        // finish small buckets right away and move the bounds of the large ones to the front of
+        // `bounds`, the rest of it is left for sorting them
+        let ends = ncounts + nbuckets + 1;
+        let mut nlarge = 0;
+        for b in 0..nbuckets {
+            let start = if b == 0 { 0 } else { bounds[ends + b - 1] };
+            let end = bounds[ends + b];
+            if end - start > CFG_INSERTION_THRESHOLD && low > 0 {
+                bounds[2 * nlarge] = start;
+                bounds[2 * nlarge + 1] = end;
+                nlarge += 1;
+            } else if end - start > 1 {
+                if low > 0 {
+                    self.insertion_sort(&mut data[start..end], low - 1);
+                }
+                if self.reversed(&data[start], round, low) {
+                    data[start..end].reverse();
+                }
+            }
+        }
+        let (large, bounds) = bounds.split_at_mut(2 * nlarge);
+        for range in large.chunks(2) {
+            let (start, end) = (range[0], range[1]);
+            self.sort(&mut data[start..end],
+                      &mut scratch[start..end],
+                      &mut digits[start..end],
+                      low - 1,
+                      bounds);
+            if self.reversed(&data[start], round, low) {
+                data[start..end].reverse();
+            }
+        }
+    }
+
+    /// Returns `true` if `x` belongs in front of `y`, where `x` came after `y` in the input. The
+    /// elements are compared by the rounds `0..(round + 1)`, equal elements keep their order
+    /// unless a bucket they share is reversed.
+    fn before(&mut self, x: &T, y: &T, round: usize) -> bool {
+        let mut rev = false;
+        for r in (0..(round + 1)).rev() {
+            let bx = self.bucket(x, r);
+            let by = self.bucket(y, r);
+            if bx != by {
+                return (bx < by) != rev;
+            }
+            rev ^= K::reverse(r, bx);
+        }
+        rev
+    }
+
+    fn insertion_sort(&mut self, data: &mut [T], round: usize) {
+        for i in 1..data.len() {
+            let mut j = i;
+            while j > 0 && self.before(&data[j], &data[j - 1], round) {
+                data.swap(j, j - 1);
+                j -= 1;
+            }
+        }
+    }
+}
+
+/// Types that are sorted by the lexicographic order of their bytes, see `rdxsort_bytes_template`.
+pub trait ByteKey {
+    fn byte_key(&self) -> &[u8];
+}
+
+impl ByteKey for String {
+    #[inline]
+    fn byte_key(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ByteKey for &str {
+    #[inline]
+    fn byte_key(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ByteKey for Vec<u8> {
+    #[inline]
+    fn byte_key(&self) -> &[u8] {
+        self
+    }
+}
+
+impl ByteKey for &[u8] {
+    #[inline]
+    fn byte_key(&self) -> &[u8] {
+        self
+    }
+}
+
+/// One bucket for "key ended" plus one per byte value.
+const CFG_BYTE_NBUCKETS: usize = 257;
+
+/// Stable MSD Radix Sort of variable-length keys. Shorter keys are sorted before longer ones that
+/// start with them, which matches the lexicographic order of `[u8]` (and of UTF-8 strings).
+///
+/// Instead of moving the elements during every round, only their indices are sorted. The final
+/// permutation is applied in-place afterwards, so `T` does not need to implement `Clone`.
+pub fn rdxsort_bytes_template<T>(data: &mut [T], desc: bool)
+    where T: ByteKey
+{
+    let n = data.len();
+
+    // early return
+    if n < 2 {
+        return;
+    }
+
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut scratch = vec![0; n];
+    let mut offsets = vec![0; CFG_BYTE_NBUCKETS];
+
+    // ranges `(start, end, depth)` that still need to be sorted, using an explicit stack keeps
+    // long common prefixes from overflowing the call stack
+    let mut todo = vec![(0, n, 0)];
+    while let Some((start, end, depth)) = todo.pop() {
+        let range = &mut perm[start..end];
+        if range.len() <= CFG_INSERTION_THRESHOLD {
+            helper_insertion_sort_bytes(data, range, depth, desc);
+            continue;
+        }
+
+        // histogram
+        for count in offsets.iter_mut() {
+            *count = 0;
+        }
+        for &i in range.iter() {
+            offsets[helper_byte_bucket(&data[i], depth)] += 1;
+        }
+
+        // trivial round, either all keys ended (so they are equal) or the next byte is shared
+        if let Some(b) = offsets.iter().position(|&count| count == range.len()) {
+            if b > 0 {
+                todo.push((start, end, depth + 1));
+            }
+            continue;
+        }
+
+        // convert counts to offsets, remember the sub-ranges of non-empty buckets
+        let mut sum = 0;
+        for i in 0..CFG_BYTE_NBUCKETS {
+            let b = if desc { CFG_BYTE_NBUCKETS - 1 - i } else { i };
+            let count = offsets[b];
+            offsets[b] = sum;
+            if b > 0 && count > 1 {
+                todo.push((start + sum, start + sum + count, depth + 1));
+            }
+            sum += count;
+        }
+        assert!(sum == range.len(), "bug: bucket size does not sum up");
+
+        // stable scatter of the indices
+        let scratch = &mut scratch[..range.len()];
+        for &i in range.iter() {
+            let pos = &mut offsets[helper_byte_bucket(&data[i], depth)];
+            scratch[*pos] = i;
+            *pos += 1;
+        }
+        range.copy_from_slice(scratch);
+    }
+
+    helper_permute(data, &perm);
+}
+
+#[inline]
+fn helper_byte_bucket<T>(x: &T, depth: usize) -> usize
+    where T: ByteKey
+{
+    match x.byte_key().get(depth) {
+        Some(&byte) => byte as usize + 1,
+        None => 0,
+    }
+}
+
+/// Stable insertion sort of the indices in `range`, whose keys share the first `depth` bytes.
+fn helper_insertion_sort_bytes<T>(data: &[T], range: &mut [usize], depth: usize, desc: bool)
+    where T: ByteKey
+{
+    let less = |i: usize, j: usize| {
+        let a = &data[i].byte_key()[depth..];
+        let b = &data[j].byte_key()[depth..];
+        if desc { a > b } else { a < b }
+    };
+
+    for i in 1..range.len() {
+        let mut j = i;
+        while j > 0 && less(range[j], range[j - 1]) {
+            range.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
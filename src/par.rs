@@ -0,0 +1,182 @@
+use super::Rdx;
+use super::sort::{ScratchGuard, helper_as_uninit, helper_assume_init, rdxsort_template_rounds};
+
+use rayon;
+use rayon::prelude::*;
+
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// Slices up to this size are sorted sequentially.
+const CFG_PAR_THRESHOLD: usize = 1 << 14;
+
+/// Parallel Radix Sort implementation for some type
+pub trait RdxParSort {
+    /// Execute Radix Sort using multiple threads, overwrites (unsorted) content of the type.
+    ///
+    /// The result is the same as for `RdxSort::rdxsort`.
+    fn par_rdxsort(&mut self);
+}
+
+/// Pointer to the scatter target, shared between threads that write to disjoint positions.
+struct SharedPtr<T>(*mut T);
+
+unsafe impl<T> Send for SharedPtr<T> where T: Send {}
+unsafe impl<T> Sync for SharedPtr<T> where T: Send {}
+
+/// Sorts `data` by the rounds `0..cfg_nrounds`. It is split into buckets using the most
+/// significant non-trivial round (MSD), afterwards all buckets are sorted in parallel using the
+/// remaining rounds (LSD).
+///
+/// The split is done in parallel as well: every thread computes a histogram for its chunk of the
+/// data and then scatters the chunk to its (precomputed) positions. Buckets that are still larger
+/// than the share of a single thread are split again, so skewed data keeps all threads busy.
+/// Slices up to `limit` elements are sorted sequentially.
+fn par_rdxsort_template<T>(data: &mut [T], cfg_nrounds: usize, limit: usize)
+    where T: Rdx + Send + Sync
+{
+    // config
+    let cfg_nbuckets = T::CFG_NBUCKETS;
+    let n = data.len();
+
+    // sequential fallback for small inputs
+    if n <= limit || cfg_nrounds == 0 {
+        rdxsort_template_rounds::<T, T, _>(data, cfg_nrounds, |x, round| x.get_bucket(round));
+        return;
+    }
+
+    let nthreads = rayon::current_num_threads();
+    let chunk_size = n.div_ceil(nthreads);
+    let limit_buckets = limit.max(n / nthreads);
+
+    // find the most significant round that does not put all elements into the same bucket,
+    // trivial rounds above it may only reverse the entire result
+    let mut reverse_all = false;
+    let mut round = cfg_nrounds - 1;
+    let histograms = loop {
+        let histograms: Vec<Vec<usize>> = data.par_chunks(chunk_size)
+            .map(|chunk| {
+                let mut histogram = vec![0; cfg_nbuckets];
+                for x in chunk {
+                    histogram[x.get_bucket(round).get()] += 1;
+                }
+                histogram
+            })
+            .collect();
+
+        let trivial = (0..cfg_nbuckets)
+            .find(|&b| histograms.iter().map(|h| h[b]).sum::<usize>() == n);
+        match trivial {
+            Some(b) => {
+                reverse_all ^= T::reverse(round, b);
+                if round == 0 {
+                    if reverse_all {
+                        data.reverse();
+                    }
+                    return;
+                }
+                round -= 1;
+            }
+            None => break histograms,
+        }
+    };
+
+    // bucket bounds and per-chunk scatter offsets
+    let mut bounds = Vec::with_capacity(cfg_nbuckets + 1);
+    let mut offsets = vec![vec![0; cfg_nbuckets]; histograms.len()];
+    let mut sum = 0;
+    for b in 0..cfg_nbuckets {
+        bounds.push(sum);
+        for (histogram, offset) in histograms.iter().zip(offsets.iter_mut()) {
+            offset[b] = sum;
+            sum += histogram[b];
+        }
+    }
+    bounds.push(sum);
+    assert!(sum == n, "bug: bucket size does not sum up");
+
+    // every chunk owns the positions from its offset up to the offset of the next chunk
+    let ends: Vec<Vec<usize>> = (0..offsets.len())
+        .map(|c| match offsets.get(c + 1) {
+            Some(next) => next.clone(),
+            None => bounds[1..].to_vec(),
+        })
+        .collect();
+
+    // parallel scatter, every chunk writes to its own, disjoint positions, elements are copied
+    // bitwise so `data` stays valid until the scatter is complete
+    let mut scratch: Vec<MaybeUninit<T>> = Vec::with_capacity(n);
+    scratch.resize_with(n, MaybeUninit::uninit);
+    {
+        let target = SharedPtr(scratch.as_mut_ptr());
+        let target = &target;
+        let complete: Vec<bool> = data.par_chunks(chunk_size)
+            .zip(offsets.into_par_iter())
+            .zip(ends.par_iter())
+            .map(|((chunk, mut offset), end)| {
+                for x in chunk {
+                    let b = x.get_bucket(round).get();
+                    assert!(offset[b] < end[b],
+                            "Your Rdx implementation returns inconsistent buckets!");
+                    unsafe {
+                        ptr::write(target.0.add(offset[b]), MaybeUninit::new(ptr::read(x)));
+                    }
+                    offset[b] += 1;
+                }
+                offset == *end
+            })
+            .collect();
+
+        // otherwise, some positions are still uninitialized
+        assert!(complete.iter().all(|&c| c),
+                "Your Rdx implementation returns inconsistent buckets!");
+    }
+
+    // from now on, the scratch buffer holds the valid elements, the guard moves them back to
+    // `data` in the end or in case of a panic
+    let guard = ScratchGuard {
+        data: unsafe { helper_as_uninit(data) },
+        scratch: &mut scratch,
+        in_scratch: true,
+    };
+
+    // sort all buckets in parallel using the remaining rounds, large ones are split again
+    let mut buckets = Vec::with_capacity(cfg_nbuckets);
+    {
+        // all `n` positions have been written exactly once
+        let mut rest = unsafe { helper_assume_init(guard.scratch) };
+        for b in 0..cfg_nbuckets {
+            let tmp = rest;
+            let (bucket, tail) = tmp.split_at_mut(bounds[b + 1] - bounds[b]);
+            buckets.push((b, bucket));
+            rest = tail;
+        }
+    }
+    buckets.into_par_iter().for_each(|(b, bucket)| {
+        par_rdxsort_template(bucket, round, limit_buckets);
+        if T::reverse(round, b) {
+            bucket.reverse();
+        }
+    });
+
+    drop(guard);
+    if reverse_all {
+        data.reverse();
+    }
+}
+
+impl<T> RdxParSort for [T]
+    where T: Rdx + Send + Sync
+{
+    fn par_rdxsort(&mut self) {
+        par_rdxsort_template(self, T::CFG_NROUNDS, CFG_PAR_THRESHOLD);
+    }
+}
+
+impl<T> RdxParSort for Vec<T>
+    where [T]: RdxParSort
+{
+    fn par_rdxsort(&mut self) {
+        self.as_mut_slice().par_rdxsort();
+    }
+}
@@ -1,102 +1,477 @@
-use super::Rdx;
+use super::{Digit, Rdx};
+use super::msd::{helper_stable_buffer_len, rdxsort_bytes_template, rdxsort_stable_template,
+                 rdxsort_unstable_template};
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::cmp::Reverse;
+use core::mem::MaybeUninit;
 use core::ptr;
+use core::slice;
 
-use std::cmp;
-use std::mem;
+/// Data sets up to this size are sorted by the stable MSD variant instead of LSD.
+pub const CFG_MSD_THRESHOLD: usize = 1 << 12;
 
 /// Radix Sort implementation for some type
 pub trait RdxSort {
     /// Execute Radix Sort, overwrites (unsorted) content of the type.
     fn rdxsort(&mut self);
+
+    /// Execute Radix Sort in descending order, overwrites (unsorted) content of the type.
+    ///
+    /// In contrast to sorting and reversing the result, this keeps the order of equal elements.
+    fn rdxsort_desc(&mut self);
+
+    /// Execute in-place Radix Sort, overwrites (unsorted) content of the type.
+    ///
+    /// This uses an MSD variant (American Flag Sort) that swaps elements instead of copying them
+    /// into buckets, so the extra memory is only `O(nbuckets * nrounds)`. The order of equal
+    /// elements is not preserved.
+    ///
+    /// The exception are `String`, `&str`, `Vec<u8>` and `&[u8]`, which use the same stable sort
+    /// as `rdxsort`. It sorts indices and permutes the data afterwards, so it requires `O(n)`
+    /// extra memory.
+    fn rdxsort_unstable(&mut self);
+}
+
+/// Radix Sort implementation for some type, using a key extracted from every element
+pub trait RdxSortByKey<T> {
+    /// Execute Radix Sort using the key returned by `f`, overwrites (unsorted) content of the
+    /// type. The element type itself does not need to implement `Rdx`.
+    ///
+    /// The sort is stable. For large data sets `f` is called up to twice per element and round:
+    /// once to build the histograms of all rounds upfront, and once more in every round that is
+    /// not skipped. Small data sets also call it for every comparison of their insertion sort. So
+    /// it should be cheap.
+    fn rdxsort_by_key<K, F>(&mut self, f: F)
+        where K: Rdx,
+              F: FnMut(&T) -> K;
+}
+
+/// Radix Sort implementation for some type, returning the sorting permutation
+pub trait RdxArgSort {
+    /// Returns the indices that sort the content of the type, without moving or cloning any
+    /// element. The permutation is stable, i.e. indices of equal elements keep their order.
+    fn rdxargsort(&self) -> Vec<usize>;
+}
+
+/// Data that can be reordered according to a permutation, see `RdxSortWith`.
+///
+/// Neither `Rdx` nor `Clone` are required for the elements.
+pub trait RdxPermute {
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reorders the elements, so that position `i` afterwards holds the element that was stored
+    /// at position `perm[i]`.
+    fn permute(&mut self, perm: &[usize]);
+}
+
+/// Radix Sort implementation for keys that are stored apart from their payload
+pub trait RdxSortWith {
+    /// Execute Radix Sort and reorder all `payloads` in lockstep with the content of the type. The
+    /// sort is stable.
+    ///
+    /// **Panics** if a payload has a different length than the sorted content.
+    fn rdxsort_with(&mut self, payloads: &mut [&mut dyn RdxPermute]);
 }
 
 #[inline]
-fn helper_bucket<T, I>(buckets_b: &mut Vec<Vec<T>>, iter: I, cfg_nbuckets: usize, round: usize)
-    where T: Rdx,
-          I: Iterator<Item = T>
+fn helper_bucket<T, K, F>(x: &T, get_bucket: &mut F, round: usize) -> usize
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
 {
-    for x in iter {
-        let b = x.get_bucket(round);
-        assert!(b < cfg_nbuckets,
-                "Your Rdx implementation returns a bucket >= cfg_nbuckets()!");
-        unsafe {
-            buckets_b.get_unchecked_mut(b).push(x);
-        }
-    }
+    get_bucket(x, round).get()
 }
 
-impl<T> RdxSort for [T]
-    where T: Rdx + Clone
+/// Views initialized elements as possibly uninitialized ones.
+///
+/// **Unsafe** because the caller must not write uninitialized values through the result.
+#[inline]
+pub unsafe fn helper_as_uninit<T>(data: &mut [T]) -> &mut [MaybeUninit<T>] {
+    slice::from_raw_parts_mut(data.as_mut_ptr() as *mut MaybeUninit<T>, data.len())
+}
+
+/// Views possibly uninitialized elements as initialized ones.
+///
+/// **Unsafe** because the caller must ensure that all elements are initialized.
+#[cfg(feature = "rayon")]
+#[inline]
+pub unsafe fn helper_assume_init<T>(data: &mut [MaybeUninit<T>]) -> &mut [T] {
+    slice::from_raw_parts_mut(data.as_mut_ptr() as *mut T, data.len())
+}
+
+/// Pair of buffers that hold bitwise copies of the same elements, where only one of them is valid.
+///
+/// If the scratch buffer is the valid one when the guard is dropped, its content is moved back to
+/// the data buffer. This also happens while unwinding, so every element is dropped exactly once,
+/// even if a `get_bucket` implementation panics.
+pub struct ScratchGuard<'a, T>
+    where T: 'a
 {
-    fn rdxsort(&mut self) {
-        // config
-        let cfg_nbuckets = T::cfg_nbuckets();
-        let cfg_nrounds = T::cfg_nrounds();
+    pub data: &'a mut [MaybeUninit<T>],
+    pub scratch: &'a mut [MaybeUninit<T>],
+    pub in_scratch: bool,
+}
 
-        // early return
-        if cfg_nrounds == 0 {
-            return;
+impl<'a, T> Drop for ScratchGuard<'a, T> {
+    fn drop(&mut self) {
+        if self.in_scratch {
+            assert!(self.data.len() == self.scratch.len(),
+                    "bug: buffer lengths do not match");
+            unsafe {
+                ptr::copy_nonoverlapping(self.scratch.as_ptr(),
+                                         self.data.as_mut_ptr(),
+                                         self.data.len());
+            }
         }
+    }
+}
 
-        let n = self.len();
-        let presize = cmp::max(16, (n << 2) / cfg_nbuckets);  // TODO: justify the presize value
-        let mut buckets_a: Vec<Vec<T>> = Vec::with_capacity(cfg_nbuckets);
-        let mut buckets_b: Vec<Vec<T>> = Vec::with_capacity(cfg_nbuckets);
-        for _ in 0..cfg_nbuckets {
-            buckets_a.push(Vec::with_capacity(presize));
-            buckets_b.push(Vec::with_capacity(presize));
+/// Moves all elements of `src` to their positions in `dst`, where `offsets` contains the next
+/// position of every bucket. Elements of reversed buckets are written from the end of the bucket
+/// towards its start, so `offsets` points behind the bucket end for them.
+///
+/// The elements are copied bitwise, so `src` stays valid until the scatter is complete.
+#[inline]
+fn helper_scatter<T, K, F>(src: &[MaybeUninit<T>],
+                           dst: &mut [MaybeUninit<T>],
+                           offsets: &mut [usize],
+                           get_bucket: &mut F,
+                           round: usize)
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    if (0..K::CFG_NBUCKETS).any(|b| K::reverse(round, b)) {
+        for x in src {
+            // all elements of the source are initialized
+            let x = unsafe { &*x.as_ptr() };
+            let b = helper_bucket(x, get_bucket, round);
+            let pos = &mut offsets[b];
+            if K::reverse(round, b) {
+                *pos -= 1;
+                dst[*pos] = MaybeUninit::new(unsafe { ptr::read(x) });
+            } else {
+                dst[*pos] = MaybeUninit::new(unsafe { ptr::read(x) });
+                *pos += 1;
+            }
         }
+    } else {
+        for x in src {
+            let x = unsafe { &*x.as_ptr() };
+            let b = helper_bucket(x, get_bucket, round);
+            let pos = &mut offsets[b];
+            dst[*pos] = MaybeUninit::new(unsafe { ptr::read(x) });
+            *pos += 1;
+        }
+    }
+}
 
-        helper_bucket(&mut buckets_a, self.iter().cloned(), cfg_nbuckets, 0);
+/// Sorts `data` using the rounds and buckets of `K`, where `get_bucket` returns the bucket of an
+/// element for a given round.
+///
+/// The histograms of all rounds are computed in a single pass upfront. Afterwards, every round
+/// moves the data from one buffer into the other, alternating between `data` and one scratch
+/// buffer of the same size. Rounds that would put all elements into the same bucket are skipped.
+///
+/// Small data sets are sorted by a stable MSD variant instead, see `rdxsort_stable_template`.
+fn rdxsort_template<T, K, F>(data: &mut [T], get_bucket: F)
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    rdxsort_template_rounds::<T, K, F>(data, K::CFG_NROUNDS, get_bucket);
+}
 
-        for round in 1..cfg_nrounds {
-            for bucket in &mut buckets_b {
-                bucket.clear();
-            }
-            for (i, bucket) in buckets_a.iter().enumerate() {
-                if T::reverse(round - 1, i) {
-                    helper_bucket(&mut buckets_b,
-                                  bucket.iter().rev().cloned(),
-                                  cfg_nbuckets,
-                                  round);
+/// Same as `rdxsort_template`, but only executes the rounds `0..cfg_nrounds`.
+pub fn rdxsort_template_rounds<T, K, F>(data: &mut [T], cfg_nrounds: usize, get_bucket: F)
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    // early return
+    if cfg_nrounds == 0 || data.len() < 2 {
+        return;
+    }
+
+    let mut scratch: Vec<MaybeUninit<T>> = Vec::with_capacity(data.len());
+    scratch.resize_with(data.len(), MaybeUninit::uninit);
+    let mut counts = vec![0usize; helper_counts_len::<K>(data.len(), cfg_nrounds)];
+    rdxsort_template_buffers::<T, K, F>(data, &mut scratch, &mut counts, cfg_nrounds, get_bucket);
+}
+
+/// Returns the number of `counts` entries `rdxsort_template_buffers` needs for `n` elements and
+/// `cfg_nrounds`.
+#[inline]
+pub fn helper_counts_len<K>(n: usize, cfg_nrounds: usize) -> usize
+    where K: Rdx
+{
+    if n <= CFG_MSD_THRESHOLD {
+        helper_stable_buffer_len::<K>(n, cfg_nrounds)
+    } else {
+        // the histograms of all rounds plus the offsets
+        K::CFG_NBUCKETS * (cfg_nrounds + 1)
+    }
+}
+
+/// Same as `rdxsort_template_rounds`, but works on buffers provided by the caller and does not
+/// allocate.
+///
+/// `scratch` must have the same length as `data` and `counts` must hold
+/// `helper_counts_len::<K>(data.len(), cfg_nrounds)` entries, the content of both is overwritten.
+pub fn rdxsort_template_buffers<T, K, F>(data: &mut [T],
+                                         scratch: &mut [MaybeUninit<T>],
+                                         counts: &mut [usize],
+                                         cfg_nrounds: usize,
+                                         mut get_bucket: F)
+    where K: Rdx,
+          F: FnMut(&T, usize) -> Digit<K>
+{
+    // config
+    let cfg_nbuckets = K::CFG_NBUCKETS;
+    let n = data.len();
+    assert!(scratch.len() == n, "scratch length does not match the data length");
+    assert!(counts.len() == helper_counts_len::<K>(n, cfg_nrounds),
+            "bug: wrong number of count entries");
+
+    // early return
+    if cfg_nrounds == 0 || n < 2 {
+        return;
+    }
+
+    // most buckets of small data sets are finished after a few rounds, which MSD exploits
+    if n <= CFG_MSD_THRESHOLD {
+        rdxsort_stable_template(data, scratch, counts, cfg_nrounds, get_bucket);
+        return;
+    }
+
+    let (histograms, counts) = counts.split_at_mut(cfg_nbuckets * cfg_nrounds);
+    let offsets = &mut counts[..cfg_nbuckets];
+    for count in histograms.iter_mut() {
+        *count = 0;
+    }
+
+    // histogram prepass
+    for x in data.iter() {
+        let mut offset = 0;
+        for round in 0..cfg_nrounds {
+            let b = helper_bucket(x, &mut get_bucket, round);
+            histograms[offset + b] += 1;
+            offset += cfg_nbuckets;
+        }
+    }
+
+    let mut guard = ScratchGuard {
+        data: unsafe { helper_as_uninit(data) },
+        scratch,
+        in_scratch: false,
+    };
+    for (round, counts) in histograms.chunks(cfg_nbuckets).enumerate() {
+        // skip trivial rounds that would put all elements into the same bucket, but still
+        // respect a possible reversion of that bucket
+        if let Some(b) = counts.iter().position(|&count| count == n) {
+            if K::reverse(round, b) {
+                if guard.in_scratch {
+                    guard.scratch.reverse();
                 } else {
-                    helper_bucket(&mut buckets_b, bucket.iter().cloned(), cfg_nbuckets, round);
+                    guard.data.reverse();
                 }
             }
-            mem::swap(&mut buckets_a, &mut buckets_b);
+            continue;
         }
 
-        let mut pos = 0;
-        for (i, bucket) in buckets_a.iter_mut().enumerate() {
-            assert!(pos + bucket.len() <= self.len(),
-                    "bug: a buckets got oversized");
-
-            if T::reverse(cfg_nrounds - 1, i) {
-                for x in bucket.iter().rev().cloned() {
-                    unsafe {
-                        *self.get_unchecked_mut(pos) = x;
-                    }
-                    pos += 1;
-                }
-            } else {
-                unsafe {
-                    ptr::copy_nonoverlapping(bucket.as_ptr(),
-                                             self.get_unchecked_mut(pos),
-                                             bucket.len());
-                }
-                pos += bucket.len();
-            }
+        // convert counts to offsets, reversed buckets are filled from their end
+        let mut sum = 0;
+        for (b, &count) in counts.iter().enumerate() {
+            offsets[b] = if K::reverse(round, b) { sum + count } else { sum };
+            sum += count;
         }
+        assert!(sum == n, "bug: bucket size does not sum up");
 
-        assert!(pos == self.len(), "bug: bucket size does not sum up");
+        if guard.in_scratch {
+            helper_scatter(guard.scratch, guard.data, offsets, &mut get_bucket, round);
+        } else {
+            helper_scatter(guard.data, guard.scratch, offsets, &mut get_bucket, round);
+        }
+
+        // every position of the target must have been written exactly once, which is not the
+        // case if `get_bucket` returned other buckets than during the histogram prepass
+        let mut sum = 0;
+        for (b, &count) in counts.iter().enumerate() {
+            let end = if K::reverse(round, b) { sum } else { sum + count };
+            assert!(offsets[b] == end,
+                    "Your Rdx implementation returns inconsistent buckets!");
+            sum += count;
+        }
+        guard.in_scratch = !guard.in_scratch;
     }
 }
 
+impl<T> RdxSort for [T]
+    where T: Rdx
+{
+    fn rdxsort(&mut self) {
+        rdxsort_template::<T, T, _>(self, |x, round| x.get_bucket(round));
+    }
+
+    fn rdxsort_desc(&mut self) {
+        // same as sorting `Reverse<T>`, but without wrapping every element
+        rdxsort_template::<T, Reverse<T>, _>(self, |x, round| x.get_bucket(round).mirror().cast());
+    }
+
+    fn rdxsort_unstable(&mut self) {
+        rdxsort_unstable_template::<T, T, _>(self, |x, round| x.get_bucket(round));
+    }
+}
+
+macro_rules! impl_rdxsort_bytes {
+    ($t:ty) => {
+        impl<'a> RdxSort for [$t] {
+            fn rdxsort(&mut self) {
+                rdxsort_bytes_template(self, false);
+            }
+
+            fn rdxsort_desc(&mut self) {
+                rdxsort_bytes_template(self, true);
+            }
+
+            fn rdxsort_unstable(&mut self) {
+                // the MSD implementation works in-place already (except for the indices)
+                rdxsort_bytes_template(self, false);
+            }
+        }
+    }
+}
+
+impl_rdxsort_bytes!(String);
+impl_rdxsort_bytes!(&'a str);
+impl_rdxsort_bytes!(Vec<u8>);
+impl_rdxsort_bytes!(&'a [u8]);
+
 impl<T> RdxSort for Vec<T>
     where [T]: RdxSort
 {
     fn rdxsort(&mut self) {
         self.as_mut_slice().rdxsort();
     }
+
+    fn rdxsort_desc(&mut self) {
+        self.as_mut_slice().rdxsort_desc();
+    }
+
+    fn rdxsort_unstable(&mut self) {
+        self.as_mut_slice().rdxsort_unstable();
+    }
+}
+
+impl<T> RdxSortByKey<T> for [T] {
+    fn rdxsort_by_key<K, F>(&mut self, mut f: F)
+        where K: Rdx,
+              F: FnMut(&T) -> K
+    {
+        rdxsort_template::<T, K, _>(self, |x, round| f(x).get_bucket(round));
+    }
+}
+
+impl<T> RdxSortByKey<T> for Vec<T> {
+    fn rdxsort_by_key<K, F>(&mut self, f: F)
+        where K: Rdx,
+              F: FnMut(&T) -> K
+    {
+        self.as_mut_slice().rdxsort_by_key(f);
+    }
+}
+
+impl<T> RdxArgSort for [T]
+    where T: Rdx
+{
+    fn rdxargsort(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        rdxsort_template::<usize, T, _>(&mut indices, |&i, round| self[i].get_bucket(round));
+        indices
+    }
+}
+
+impl<T> RdxArgSort for Vec<T>
+    where [T]: RdxArgSort
+{
+    fn rdxargsort(&self) -> Vec<usize> {
+        self.as_slice().rdxargsort()
+    }
+}
+
+/// Applies `perm` in-place by following its cycles and swapping elements along the way.
+pub fn helper_permute<T>(data: &mut [T], perm: &[usize]) {
+    assert!(data.len() == perm.len(),
+            "permutation length does not match the data length");
+
+    let mut done = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        if done[start] {
+            continue;
+        }
+        done[start] = true;
+
+        let mut current = start;
+        loop {
+            let next = perm[current];
+            if next == start {
+                break;
+            }
+            assert!(!done[next], "not a valid permutation");
+            data.swap(current, next);
+            done[next] = true;
+            current = next;
+        }
+    }
+}
+
+impl<T> RdxPermute for Vec<T> {
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn permute(&mut self, perm: &[usize]) {
+        helper_permute(self, perm);
+    }
+}
+
+impl<T> RdxPermute for &mut [T] {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn permute(&mut self, perm: &[usize]) {
+        helper_permute(self, perm);
+    }
+}
+
+impl<T> RdxSortWith for [T]
+    where T: Rdx
+{
+    fn rdxsort_with(&mut self, payloads: &mut [&mut dyn RdxPermute]) {
+        // check everything upfront, so nothing gets reordered in case of an error
+        for payload in payloads.iter() {
+            assert!(payload.len() == self.len(),
+                    "payload length does not match the key length");
+        }
+
+        let perm = self.rdxargsort();
+        helper_permute(self, &perm);
+        for payload in payloads.iter_mut() {
+            payload.permute(&perm);
+        }
+    }
+}
+
+impl<T> RdxSortWith for Vec<T>
+    where [T]: RdxSortWith
+{
+    fn rdxsort_with(&mut self, payloads: &mut [&mut dyn RdxPermute]) {
+        self.as_mut_slice().rdxsort_with(payloads);
+    }
 }
@@ -0,0 +1,80 @@
+use super::Rdx;
+use super::sort::{helper_counts_len, rdxsort_template_buffers, CFG_MSD_THRESHOLD};
+
+use alloc::vec::Vec;
+
+use core::cmp::Reverse;
+use core::mem::MaybeUninit;
+
+/// Reusable context for stable Radix Sort.
+///
+/// Caches the scratch buffer and the histograms across calls, so sorting does not allocate once
+/// the buffers are large enough for the data. This is useful when many small data sets are
+/// sorted, e.g. in a latency-sensitive loop.
+pub struct RdxSorter<T>
+    where T: Rdx
+{
+    scratch: Vec<MaybeUninit<T>>,
+    counts: Vec<usize>,
+}
+
+impl<T> RdxSorter<T>
+    where T: Rdx
+{
+    /// Creates a new sorter without allocating.
+    pub fn new() -> RdxSorter<T> {
+        RdxSorter {
+            scratch: Vec::new(),
+            counts: Vec::new(),
+        }
+    }
+
+    /// Creates a new sorter that can sort up to `capacity` elements without allocating.
+    pub fn with_capacity(capacity: usize) -> RdxSorter<T> {
+        // small data sets need more entries than the large ones that use LSD
+        let small = helper_counts_len::<T>(capacity.min(CFG_MSD_THRESHOLD), T::CFG_NROUNDS);
+        let large = helper_counts_len::<T>(capacity, T::CFG_NROUNDS);
+        RdxSorter {
+            scratch: Vec::with_capacity(capacity),
+            counts: Vec::with_capacity(small.max(large)),
+        }
+    }
+
+    /// Same as `RdxSort::rdxsort`.
+    pub fn sort(&mut self, data: &mut [T]) {
+        self.prepare(data);
+        rdxsort_template_buffers::<T, T, _>(data,
+                                            &mut self.scratch,
+                                            &mut self.counts,
+                                            T::CFG_NROUNDS,
+                                            |x, round| x.get_bucket(round));
+    }
+
+    /// Same as `RdxSort::rdxsort_desc`.
+    pub fn sort_desc(&mut self, data: &mut [T]) {
+        self.prepare(data);
+        rdxsort_template_buffers::<T, Reverse<T>, _>(data,
+                                                     &mut self.scratch,
+                                                     &mut self.counts,
+                                                     T::CFG_NROUNDS,
+                                                     |x, round| {
+                                                         x.get_bucket(round).mirror().cast()
+                                                     });
+    }
+
+    /// Resizes all buffers for `data`, only allocates if they are too small.
+    fn prepare(&mut self, data: &[T]) {
+        self.scratch.clear();
+        self.scratch.resize_with(data.len(), MaybeUninit::uninit);
+
+        self.counts.resize(helper_counts_len::<T>(data.len(), T::CFG_NROUNDS), 0);
+    }
+}
+
+impl<T> Default for RdxSorter<T>
+    where T: Rdx
+{
+    fn default() -> RdxSorter<T> {
+        RdxSorter::new()
+    }
+}
@@ -1,48 +1,55 @@
 use super::Rdx;
+use super::map::{Entry, RdxMap, RdxMapIntoIter, RdxMapKeys, RdxMapRange};
 
-use std::cell::RefCell;
-use std::marker::PhantomData;
-use std::fmt;
-use std::rc::Rc;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
+use core::mem;
+use core::ops::RangeBounds;
 
-type RcInner<T> = Rc<RefCell<NodeInner<T>>>;
-type RcPruned<T> = Rc<RefCell<NodePruned<T>>>;
+#[cfg(feature = "std")]
+use std::fmt;
 
 
-#[derive(Clone)]
-enum Node<T>
-    where T: Clone + Rdx
+/// Slot of the tree.
+///
+/// The round of a slot is the number of digits that are left to describe its elements, so leafs
+/// (`Child`) are located at round `0`.
+pub enum Node<K, V>
+    where K: Rdx
 {
-    Inner(RcInner<T>),
-    Pruned(RcPruned<T>),
-    Child(T),
+    Inner(Box<NodeInner<K, V>>),
+    Pruned(Box<NodePruned<K, V>>),
+    Child(K, V),
     Free,
 }
 
 
-impl<T> Node<T>
-    where T: Clone + Rdx
+#[cfg(feature = "std")]
+impl<K, V> Node<K, V>
+    where K: Rdx
 {
     fn print(&self, depth: usize)
-        where T: fmt::Display
+        where K: fmt::Display
     {
         let prefix: String = (0..depth).map(|_| ' ').collect();
         match *self {
             Node::Inner(ref inner) => {
-                for (i, c) in inner.borrow().children.iter().enumerate() {
-                    println!("{}{}:", prefix, i);
-                    c.print(depth + 1);
-                }
+                inner.print(depth);
             }
             Node::Pruned(ref pruned) => {
-                let borrowed = pruned.borrow();
-                println!("{}P: [{:?}]", prefix, borrowed.buckets);
-                let c: Node<T> = (&(borrowed.child)).into();
-                c.print(depth + borrowed.buckets.len());
+                println!("{}P: [{:?}]", prefix, pruned.buckets);
+                let depth = depth + pruned.buckets.len();
+                match pruned.child {
+                    NodeLimited::Inner(ref inner) => inner.print(depth),
+                    NodeLimited::Child(ref k, _) => {
+                        let prefix: String = (0..depth).map(|_| ' ').collect();
+                        println!("{}=> {}", prefix, k);
+                    }
+                }
             }
-            Node::Child(ref x) => {
-                println!("{}=> {}", prefix, x);
+            Node::Child(ref k, _) => {
+                println!("{}=> {}", prefix, k);
             }
             Node::Free => {
                 println!("{}X", prefix);
@@ -52,120 +59,361 @@ impl<T> Node<T>
 }
 
 
-#[derive(Clone)]
-enum NodeLimited<T>
-    where T: Clone + Rdx
+impl<K, V> Node<K, V>
+    where K: Rdx
 {
-    Inner(RcInner<T>),
-    Child(T),
+    /// Creates a pruned path (or a leaf for round `0`) that only contains `k`.
+    fn new_path(round: usize, k: K, v: V) -> Node<K, V> {
+        if round == 0 {
+            Node::Child(k, v)
+        } else {
+            Node::Pruned(Box::new(NodePruned::new(round, k, v)))
+        }
+    }
+
+    /// Inserts `k` into the subtree that starts at this slot and returns a reference to the value
+    /// of the element, together with the value that got replaced, if any. If an equal key is
+    /// present already, it is kept and `k` is dropped.
+    fn insert(&mut self, round: usize, k: K, v: V) -> (&mut V, Option<V>) {
+        // split pruned paths that diverge from `k` first, afterwards `k` can follow the path
+        let split = match *self {
+            Node::Pruned(ref pruned) => pruned.mismatch(&k),
+            _ => None,
+        };
+        if let Some(i) = split {
+            self.split(i);
+        }
+
+        match *self {
+            Node::Free => {
+                *self = Node::new_path(round, k, v);
+                (self.value_mut(), None)
+            }
+            Node::Child(_, ref mut v2) => {
+                let replaced = mem::replace(v2, v);
+                (v2, Some(replaced))
+            }
+            Node::Inner(ref mut inner) => inner.insert(k, v),
+            Node::Pruned(ref mut pruned) => {
+                match pruned.child {
+                    NodeLimited::Inner(ref mut inner) => inner.insert(k, v),
+                    NodeLimited::Child(_, ref mut v2) => {
+                        let replaced = mem::replace(v2, v);
+                        (v2, Some(replaced))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the value of a path that was just created using `new_path`.
+    fn value_mut(&mut self) -> &mut V {
+        match *self {
+            Node::Child(_, ref mut v) => v,
+            Node::Pruned(ref mut pruned) => {
+                match pruned.child {
+                    NodeLimited::Child(_, ref mut v) => v,
+                    NodeLimited::Inner(_) => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Splits the pruned path of this slot at bucket `i`.
+    ///
+    /// ```text
+    ///     [head][middle/diff][tail]
+    /// ```
+    ///
+    /// becomes
+    ///
+    /// ```text
+    ///                     |-[tail]
+    ///     [head]-[middle]-|
+    ///                     |-(free)
+    /// ```
+    fn split(&mut self, i: usize) {
+        let pruned = match mem::replace(self, Node::Free) {
+            Node::Pruned(pruned) => *pruned,
+            _ => unreachable!(),
+        };
+        let NodePruned { round, mut buckets, child } = pruned;
+
+        // split head, middle and tail
+        let buckets_tail = buckets.split_off(i + 1);
+        let bucket = buckets.pop().expect("bug: split outside of the path");
+
+        // inner node = middle part, add old tail to it
+        let mut inner = NodeInner::new(round - i);
+        inner.children[bucket] = if buckets_tail.is_empty() {
+            child.into()
+        } else {
+            Node::Pruned(Box::new(NodePruned {
+                round: round - i - 1,
+                buckets: buckets_tail,
+                child,
+            }))
+        };
+
+        // either use inner node directly (when head is empty) or create new head
+        *self = if buckets.is_empty() {
+            Node::Inner(Box::new(inner))
+        } else {
+            Node::Pruned(Box::new(NodePruned {
+                round,
+                buckets,
+                child: NodeLimited::Inner(Box::new(inner)),
+            }))
+        };
+    }
+
+    /// Looks up the element that is equal to `k`.
+    pub fn find(&self, k: &K) -> Option<(&K, &V)> {
+        match *self {
+            Node::Free => None,
+            Node::Child(ref k2, ref v) => Some((k2, v)),
+            Node::Inner(ref inner) => inner.find(k),
+            Node::Pruned(ref pruned) => {
+                if pruned.mismatch(k).is_some() {
+                    return None;
+                }
+                match pruned.child {
+                    NodeLimited::Inner(ref inner) => inner.find(k),
+                    NodeLimited::Child(ref k2, ref v) => Some((k2, v)),
+                }
+            }
+        }
+    }
+
+    /// Same as `find`, but returns mutable references. The key must only be replaced by an equal
+    /// one.
+    pub fn find_mut(&mut self, k: &K) -> Option<(&mut K, &mut V)> {
+        match *self {
+            Node::Free => None,
+            Node::Child(ref mut k2, ref mut v) => Some((k2, v)),
+            Node::Inner(ref mut inner) => inner.find_mut(k),
+            Node::Pruned(ref mut pruned) => {
+                if pruned.mismatch(k).is_some() {
+                    return None;
+                }
+                match pruned.child {
+                    NodeLimited::Inner(ref mut inner) => inner.find_mut(k),
+                    NodeLimited::Child(ref mut k2, ref mut v) => Some((k2, v)),
+                }
+            }
+        }
+    }
+
+    /// Removes the element that is equal to `k` and returns it.
+    ///
+    /// Inner nodes that are left with a single child are collapsed into pruned paths.
+    fn take(&mut self, k: &K) -> Option<(K, V)> {
+        let taken = match *self {
+            Node::Free => return None,
+            Node::Child(..) => None,
+            Node::Inner(ref mut inner) => Some(inner.take(k)?),
+            Node::Pruned(ref mut pruned) => {
+                if pruned.mismatch(k).is_some() {
+                    return None;
+                }
+                match pruned.child {
+                    NodeLimited::Inner(ref mut inner) => Some(inner.take(k)?),
+                    NodeLimited::Child(..) => None,
+                }
+            }
+        };
+
+        match taken {
+            Some(x) => {
+                self.shrink();
+                Some(x)
+            }
+            None => {
+                // reached the leaf (directly or at the end of a path) => free the entire slot
+                match mem::replace(self, Node::Free) {
+                    Node::Child(k, v) => Some((k, v)),
+                    Node::Pruned(pruned) => {
+                        match pruned.child {
+                            NodeLimited::Child(k, v) => Some((k, v)),
+                            NodeLimited::Inner(_) => unreachable!(),
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    /// Collapses the inner node of this slot (or at the end of its path) if it only has a single
+    /// child left.
+    fn shrink(&mut self) {
+        let collapse = match *self {
+            Node::Inner(ref inner) => inner.nused() == 1,
+            Node::Pruned(ref pruned) => {
+                match pruned.child {
+                    NodeLimited::Inner(ref inner) => inner.nused() == 1,
+                    NodeLimited::Child(..) => false,
+                }
+            }
+            _ => false,
+        };
+        if !collapse {
+            return;
+        }
+
+        *self = match mem::replace(self, Node::Free) {
+            Node::Inner(inner) => Node::Pruned(Box::new((*inner).collapse())),
+            Node::Pruned(pruned) => {
+                // merge paths
+                let NodePruned { round, mut buckets, child } = *pruned;
+                let tail = match child {
+                    NodeLimited::Inner(inner) => (*inner).collapse(),
+                    NodeLimited::Child(..) => unreachable!(),
+                };
+                buckets.extend(tail.buckets);
+                Node::Pruned(Box::new(NodePruned {
+                    round,
+                    buckets,
+                    child: tail.child,
+                }))
+            }
+            _ => unreachable!(),
+        };
+    }
 }
 
 
-impl<'a, T> From<&'a NodeLimited<T>> for Node<T>
-    where T: Clone + Rdx
+/// End of a pruned path, which is either an inner node or a leaf.
+pub enum NodeLimited<K, V>
+    where K: Rdx
 {
-    fn from(obj: &'a NodeLimited<T>) -> Node<T> {
-        match *obj {
-            NodeLimited::Inner(ref inner) => Node::Inner(inner.clone()),
-            NodeLimited::Child(ref x) => Node::Child(x.clone()),
+    Inner(Box<NodeInner<K, V>>),
+    Child(K, V),
+}
+
+
+impl<K, V> From<NodeLimited<K, V>> for Node<K, V>
+    where K: Rdx
+{
+    fn from(obj: NodeLimited<K, V>) -> Node<K, V> {
+        match obj {
+            NodeLimited::Inner(inner) => Node::Inner(inner),
+            NodeLimited::Child(k, v) => Node::Child(k, v),
         }
     }
 }
 
 
-#[derive(Clone)]
-struct NodeInner<T>
-    where T: Clone + Rdx
+/// Node that has one slot per bucket of round `round - 1`.
+pub struct NodeInner<K, V>
+    where K: Rdx
 {
-    round: usize,
-    children: Vec<Node<T>>,
+    pub round: usize,
+    pub children: Vec<Node<K, V>>,
 }
 
 
-#[derive(Clone)]
-struct NodePruned<T>
-    where T: Clone + Rdx
+/// Path of nodes that only have a single child each, `buckets[i]` is the bucket of round
+/// `round - 1 - i`.
+pub struct NodePruned<K, V>
+    where K: Rdx
 {
-    round: usize,
-    nbuckets: usize,
-    buckets: Vec<usize>,
-    child: NodeLimited<T>,
+    pub round: usize,
+    pub buckets: Vec<usize>,
+    pub child: NodeLimited<K, V>,
 }
 
 
-impl<T> NodeInner<T>
-    where T: Clone + Rdx
+impl<K, V> NodeInner<K, V>
+    where K: Rdx
 {
-    fn new(round: usize, nbuckets: usize) -> NodeInner<T> {
-        let mut children = Vec::with_capacity(nbuckets);
-        for _ in 0..nbuckets {
+    pub fn new(round: usize) -> NodeInner<K, V> {
+        let mut children = Vec::with_capacity(K::CFG_NBUCKETS);
+        for _ in 0..K::CFG_NBUCKETS {
             children.push(Node::Free);
         }
         NodeInner {
-            round: round,
-            children: children,
+            round,
+            children,
         }
     }
 
-    fn insert(&mut self, x: T) {
-        let bucket = x.get_bucket(self.round - 1);
+    pub fn insert(&mut self, k: K, v: V) -> (&mut V, Option<V>) {
+        let bucket = k.get_bucket(self.round - 1).get();
+        self.children[bucket].insert(self.round - 1, k, v)
+    }
 
-        if self.round > 1 {
-            let clen = self.children.len();
-            let replace = match self.children[bucket] {
-                Node::Free => {
-                    let pruned = Rc::new(RefCell::new(NodePruned::new(self.round - 1, clen, x)));
-                    Some(Node::Pruned(pruned))
-                }
-                Node::Inner(ref mut inner) => {
-                    inner.borrow_mut().insert(x);
-                    None
-                }
-                Node::Pruned(ref mut pruned) => Some(pruned.borrow().insert_or_split(x)),
-                Node::Child(_) => unreachable!(),
-            };
+    pub fn find(&self, k: &K) -> Option<(&K, &V)> {
+        let bucket = k.get_bucket(self.round - 1).get();
+        self.children[bucket].find(k)
+    }
 
-            if let Some(obj) = replace {
-                self.children[bucket] = obj;
-            }
-        } else {
-            let alloc = match self.children[bucket] {
-                Node::Free => true,
-                Node::Child(_) => false,
-                Node::Inner(_) => unreachable!(),
-                Node::Pruned(_) => unreachable!(),
-            };
-
-            if alloc {
-                self.children[bucket] = Node::Child(x);
-            } else {
-                match self.children[bucket] {
-                    Node::Child(ref mut y) => *y = x,  // XXX: is that a good idea?
-                    _ => unreachable!(),
-                }
+    pub fn find_mut(&mut self, k: &K) -> Option<(&mut K, &mut V)> {
+        let bucket = k.get_bucket(self.round - 1).get();
+        self.children[bucket].find_mut(k)
+    }
+
+    /// Removes the element that is equal to `k` and returns it.
+    ///
+    /// The node itself is not collapsed, this is done by the slot that holds it.
+    pub fn take(&mut self, k: &K) -> Option<(K, V)> {
+        let bucket = k.get_bucket(self.round - 1).get();
+        self.children[bucket].take(k)
+    }
+
+    /// Returns the number of children that are not free.
+    fn nused(&self) -> usize {
+        self.children.iter().filter(|c| !matches!(**c, Node::Free)).count()
+    }
+
+    /// Converts a node with a single child into a pruned path.
+    fn collapse(self) -> NodePruned<K, V> {
+        let round = self.round;
+        let (bucket, child) = self.children
+            .into_iter()
+            .enumerate()
+            .find(|(_, c)| !matches!(*c, Node::Free))
+            .expect("bug: cannot collapse empty node");
+
+        let mut buckets = vec![bucket];
+        let child = match child {
+            Node::Inner(inner) => NodeLimited::Inner(inner),
+            Node::Pruned(pruned) => {
+                // merge paths
+                let pruned = *pruned;
+                buckets.extend(pruned.buckets);
+                pruned.child
             }
+            Node::Child(k, v) => NodeLimited::Child(k, v),
+            Node::Free => unreachable!(),
+        };
+        NodePruned {
+            round,
+            buckets,
+            child,
         }
     }
 
-    fn nnodes(&self) -> (usize, usize, usize, usize) {
+    pub fn nnodes(&self) -> (usize, usize, usize, usize) {
         let mut result = (1, 0, 0, 0);
         for c in &self.children {
             match *c {
                 Node::Inner(ref inner) => {
-                    let tmp = inner.borrow().nnodes();
+                    let tmp = inner.nnodes();
                     result.0 += tmp.0;
                     result.1 += tmp.1;
                     result.2 += tmp.2;
                     result.3 += tmp.3;
                 }
                 Node::Pruned(ref pruned) => {
-                    let tmp = pruned.borrow().nnodes();
+                    let tmp = pruned.nnodes();
                     result.0 += tmp.0;
                     result.1 += tmp.1;
                     result.2 += tmp.2;
                     result.3 += tmp.3;
                 }
-                Node::Child(_) => {
+                Node::Child(..) => {
                     result.2 += 1;
                 }
                 Node::Free => {
@@ -175,109 +423,57 @@ impl<T> NodeInner<T>
         }
         result
     }
+
+    #[cfg(feature = "std")]
+    pub fn print(&self, depth: usize)
+        where K: fmt::Display
+    {
+        let prefix: String = (0..depth).map(|_| ' ').collect();
+        for (i, c) in self.children.iter().enumerate() {
+            println!("{}{}:", prefix, i);
+            c.print(depth + 1);
+        }
+    }
 }
 
 
-impl<T> NodePruned<T>
-    where T: Clone + Rdx
+impl<K, V> NodePruned<K, V>
+    where K: Rdx
 {
-    fn new(round: usize, nbuckets: usize, x: T) -> NodePruned<T> {
+    fn new(round: usize, k: K, v: V) -> NodePruned<K, V> {
         let mut buckets = Vec::with_capacity(round);
         for i in 0..round {
             let r = round - i;
-            let bucket = x.get_bucket(r - 1);
+            let bucket = k.get_bucket(r - 1).get();
             buckets.push(bucket);
         }
-        let child = NodeLimited::Child(x);
+        let child = NodeLimited::Child(k, v);
         NodePruned {
-            round: round,
-            nbuckets: nbuckets,
-            buckets: buckets,
-            child: child,
+            round,
+            buckets,
+            child,
         }
     }
 
-    fn insert_or_split(&self, x: T) -> Node<T> {
-        for i in 0..self.buckets.len() {
-            let r = self.round - i;
-            let bucket_y = self.buckets[i];
-            let bucket_x = x.get_bucket(r - 1);
-
-            if bucket_x != bucket_y {
-                // === outcome a: split ===
-                //
-                //     [head][middle/diff][tail]
-                //
-                // becomes
-                //
-                //                     |-[tail1]
-                //     [head]-[middle]-|
-                //                     |-[tail2]
-                //
-
-                // split head, middle and tail
-                let mut buckets_head = self.buckets.clone();
-                let buckets_tail = buckets_head.split_off(i + 1);
-                buckets_head.pop();  // remove middle part
-
-                // inner node = middle part
-                let mut inner = NodeInner::new(self.round - buckets_head.len(), self.nbuckets);
-
-                // add old tail and new branch to inner node
-                if buckets_tail.is_empty() {
-                    inner.children[bucket_y] = (&self.child).into();
-                } else {
-                    let tail = Rc::new(RefCell::new(NodePruned {
-                        round: self.round - i - 1,
-                        nbuckets: self.nbuckets,
-                        buckets: buckets_tail,
-                        child: self.child.clone(),
-                    }));
-                    inner.children[bucket_y] = Node::Pruned(tail);
-                }
-                inner.insert(x);
-
-                // either return inner node (when head is empty) or create new head
-                if buckets_head.is_empty() {
-                    return Node::Inner(Rc::new(RefCell::new(inner)));
-                } else {
-                    let head = Rc::new(RefCell::new(NodePruned {
-                        round: self.round,
-                        nbuckets: self.nbuckets,
-                        buckets: buckets_head,
-                        child: NodeLimited::Inner(Rc::new(RefCell::new(inner))),
-                    }));
-                    return Node::Pruned(head);
-                }
-            }
-        }
-
-        // === outcome b: insert ===
-        // INFO: Copying seems to be faster than returning an Option and do the change in-place.
-        //       I don't know why this is the case.
-        let mut cpy = self.clone();
-        match cpy.child {
-            NodeLimited::Inner(ref mut inner) => {
-                inner.borrow_mut().insert(x);
-            }
-            NodeLimited::Child(ref mut y) => {
-                *y = x;
-            }
-        }
-        Node::Pruned(Rc::new(RefCell::new(cpy)))
+    /// Returns the position of the first bucket of the path that does not match `k`.
+    fn mismatch(&self, k: &K) -> Option<usize> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .position(|(i, &bucket)| k.get_bucket(self.round - i - 1).get() != bucket)
     }
 
     fn nnodes(&self) -> (usize, usize, usize, usize) {
         let mut result = (0, 1, 0, 0);
         match self.child {
             NodeLimited::Inner(ref inner) => {
-                let tmp = inner.borrow().nnodes();
+                let tmp = inner.nnodes();
                 result.0 += tmp.0;
                 result.1 += tmp.1;
                 result.2 += tmp.2;
                 result.3 += tmp.3;
             }
-            NodeLimited::Child(_) => {
+            NodeLimited::Child(..) => {
                 result.2 += 1;
             }
         }
@@ -286,164 +482,249 @@ impl<T> NodePruned<T>
 }
 
 
+/// Ordered set of `Rdx` elements, based on a radix tree.
+///
+/// All nodes are owned by their parents, so the tree is `Send` and `Sync` if `T` is. A shared
+/// tree, e.g. behind an `Arc`, can be read and iterated by multiple threads at the same time.
 pub struct RdxTree<T>
-    where T: Clone + Rdx
+    where T: Rdx
 {
-    root: Node<T>,
+    map: RdxMap<T, ()>,
 }
 
 
 impl<T> RdxTree<T>
-    where T: Clone + Rdx
+    where T: Rdx
 {
     pub fn new() -> RdxTree<T> {
-        let rounds = <T as Rdx>::cfg_nrounds();
-        let buckets = <T as Rdx>::cfg_nbuckets();
-        RdxTree { root: Node::Inner(Rc::new(RefCell::new(NodeInner::<T>::new(rounds, buckets)))) }
+        RdxTree { map: RdxMap::new() }
     }
 
-    pub fn insert(&mut self, x: T) {
-        match self.root {
-            Node::Inner(ref mut inner) => {
-                inner.borrow_mut().insert(x);
-            }
-            _ => {
-                unreachable!();
+    /// Adds `x` to the tree and returns `true` if no equal element was present. Otherwise, the
+    /// tree is left unchanged, see `replace` for overwriting the stored element.
+    pub fn insert(&mut self, x: T) -> bool {
+        match self.map.entry(x) {
+            Entry::Vacant(entry) => {
+                entry.insert(());
+                true
             }
+            Entry::Occupied(_) => false,
         }
     }
 
-    pub fn iter<'a>(&self) -> RdxTreeIter<'a, T> {
-        let mut stack = Vec::new();
-        match self.root {
-            Node::Inner(ref inner) => {
-                stack.push(((inner.clone(), 1, false)));
+    /// Adds `x` to the tree and returns the equal element that got replaced, if any.
+    pub fn replace(&mut self, x: T) -> Option<T> {
+        match self.map.get_key_mut(&x) {
+            Some(y) => Some(mem::replace(y, x)),
+            None => {
+                self.map.insert(x, ());
+                None
             }
-            _ => unreachable!(),
-        }
-        RdxTreeIter {
-            stack: stack,
-            phantom: PhantomData,
         }
     }
 
+    /// Returns `true` if the tree contains an element that is equal to `x`.
+    pub fn contains(&self, x: &T) -> bool {
+        self.map.contains_key(x)
+    }
+
+    /// Returns the stored element that is equal to `x`.
+    pub fn get(&self, x: &T) -> Option<&T> {
+        self.map.get_key_value(x).map(|(y, _)| y)
+    }
+
+    /// Removes the element that is equal to `x` and returns `true` if it was present.
+    pub fn remove(&mut self, x: &T) -> bool {
+        self.take(x).is_some()
+    }
+
+    /// Removes the element that is equal to `x` and returns it.
+    pub fn take(&mut self, x: &T) -> Option<T> {
+        self.map.remove_entry(x).map(|(y, ())| y)
+    }
+
+    /// Returns the number of elements in the tree.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the tree does not contain any element.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns an iterator over all elements in ascending order.
+    pub fn iter(&self) -> RdxTreeIter<'_, T> {
+        RdxTreeIter { keys: self.map.keys() }
+    }
+
+    /// Returns an iterator over the elements within `range`, in ascending order.
+    pub fn range<R>(&self, range: R) -> RdxTreeRange<'_, T>
+        where R: RangeBounds<T>
+    {
+        RdxTreeRange { range: self.map.range(range) }
+    }
+
+    /// Returns the smallest element.
+    pub fn first(&self) -> Option<&T> {
+        self.map.first_key_value().map(|(x, _)| x)
+    }
+
+    /// Returns the largest element.
+    pub fn last(&self) -> Option<&T> {
+        self.map.last_key_value().map(|(x, _)| x)
+    }
+
+    /// Returns the largest element that is less than or equal to `x`.
+    pub fn floor(&self, x: &T) -> Option<&T> {
+        self.map.floor_key_value(x).map(|(y, _)| y)
+    }
+
+    /// Returns the smallest element that is greater than or equal to `x`.
+    pub fn ceiling(&self, x: &T) -> Option<&T> {
+        self.map.ceiling_key_value(x).map(|(y, _)| y)
+    }
+
     pub fn nnodes(&self) -> (usize, usize, usize, usize) {
-        match self.root {
-            Node::Inner(ref inner) => inner.borrow().nnodes(),
-            _ => unreachable!(),
-        }
+        self.map.nnodes()
     }
 
+    #[cfg(feature = "std")]
     pub fn print(&self)
         where T: fmt::Display
     {
-        self.root.print(0);
+        self.map.root().print(0);
+    }
+}
+
+
+impl<T> Default for RdxTree<T>
+    where T: Rdx
+{
+    fn default() -> RdxTree<T> {
+        RdxTree::new()
     }
 }
 
 
 pub struct RdxTreeIter<'a, T>
-    where T: Clone + Rdx + 'a
+    where T: Rdx + 'a
 {
-    // iterator stack:
-    //   - reference to inner node
-    //     (do not work with iterators directly since we need a checked but dynamic borrow)
-    //   - current iterator state + 1 (so `0` encodes the "the one BEFORE beginning)
-    //   - reverse the iterator order for this subpart if `True`
-    stack: Vec<(RcInner<T>, usize, bool)>,
-
-    // keep tree borrow intact
-    phantom: PhantomData<&'a RdxTree<T>>,
+    keys: RdxMapKeys<'a, T, ()>,
 }
 
 
 impl<'a, T> Iterator for RdxTreeIter<'a, T>
-    where T: Clone + Rdx + 'a
+    where T: Rdx + 'a
 {
-    type Item = T;  // XXX: do not copy!
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // the iteration is basically the processing of a stack machine
-
-        let mut result: Option<T> = None;
-
-        // iterate until stack is empty or we have a result
-        while !self.stack.is_empty() && result.is_none() {
-            // the stack is immutable since we work with the current state,
-            // therefore we need to store pending operations (push or pop) and execute afterwards
-            let mut push: Option<(Rc<RefCell<NodeInner<T>>>, bool)> = None;
-            let mut pop = false;
-            let stacksize = self.stack.len();
-
-            if let Some(state) = self.stack.last_mut() {
-                let &mut (ref rc, ref mut i, reverse) = state;
-                let borrowed = rc.borrow();
-
-                // bounds check for current iterator state
-                if (reverse && (*i == 0)) || (*i >= borrowed.children.len() + 1) {
-                    pop = true;
-                } else {
-                    // bounds are fine => inspect current sub-element
-                    match borrowed.children[*i - 1] {
-                        Node::Free => {
-                            // it's a free node, we can ignore that and continue with the iteration
-                        }
-                        Node::Child(ref x) => {
-                            // we have found some usable data :)
-                            result = Some(x.clone());
-                        }
-                        Node::Inner(ref inner) => {
-                            // inner node => push a new state to the stack
-                            let round = <T as Rdx>::cfg_nrounds() - stacksize;
-                            let rev = reverse ^ <T as Rdx>::reverse(round, *i - 1);
-                            push = Some((inner.clone(), rev));
-                        }
-                        Node::Pruned(ref pruned) => {
-                            // pruned tree part => let's check what the child is
-                            let borrowed2 = pruned.borrow();
-                            match borrowed2.child {
-                                NodeLimited::Child(ref x) => {
-                                    // usable data :)
-                                    result = Some(x.clone());
-                                }
-                                NodeLimited::Inner(ref inner) => {
-                                    // simulate traversal of pruned tree part to recover `reverse`
-                                    let mut round = <T as Rdx>::cfg_nrounds() - stacksize;
-                                    let mut rev = reverse ^ <T as Rdx>::reverse(round, *i - 1);
-                                    for j in &borrowed2.buckets {
-                                        round += 1;
-                                        rev ^= <T as Rdx>::reverse(round, *j);
-                                    }
-
-                                    push = Some((inner.clone(), rev));
-                                }
-                            }
-                        }
-                    }
+        self.keys.next()
+    }
 
-                    if reverse {
-                        *i -= 1;
-                    } else {
-                        *i += 1;
-                    }
-                }
-            } else {
-                // that cannot happen since we have already checked if the stack is not empty
-                unreachable!();
-            }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
 
-            // execute all pending operations
-            if pop {
-                self.stack.pop();
-            } else if let Some((next, rev)) = push {
-                // the iteration of the next stack part starts either at the beginning or end,
-                // depending on the fact that it is a reversed iteration or not
-                let idx_start = if rev { next.borrow().children.len() } else { 1 };
-                self.stack.push((next, idx_start, rev));
-            }
-        }
 
-        // this can be `None` here in case we've finished iteration and the stack is empty
-        result
+impl<'a, T> DoubleEndedIterator for RdxTreeIter<'a, T>
+    where T: Rdx + 'a
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.keys.next_back()
+    }
+}
+
+
+impl<'a, T> ExactSizeIterator for RdxTreeIter<'a, T>
+    where T: Rdx + 'a
+{
+}
+
+
+impl<'a, T> IntoIterator for &'a RdxTree<T>
+    where T: Rdx
+{
+    type Item = &'a T;
+    type IntoIter = RdxTreeIter<'a, T>;
+
+    fn into_iter(self) -> RdxTreeIter<'a, T> {
+        self.iter()
+    }
+}
+
+
+/// Owning iterator over the elements of a `RdxTree`, see `RdxTree::into_iter`.
+pub struct RdxTreeIntoIter<T> {
+    iter: RdxMapIntoIter<T, ()>,
+}
+
+
+impl<T> Iterator for RdxTreeIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(x, ())| x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<T> DoubleEndedIterator for RdxTreeIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(x, ())| x)
+    }
+}
+
+
+impl<T> ExactSizeIterator for RdxTreeIntoIter<T> {}
+
+
+impl<T> IntoIterator for RdxTree<T>
+    where T: Rdx
+{
+    type Item = T;
+    type IntoIter = RdxTreeIntoIter<T>;
+
+    /// Moves all elements out of the tree, in ascending order.
+    fn into_iter(self) -> RdxTreeIntoIter<T> {
+        RdxTreeIntoIter { iter: self.map.into_iter() }
+    }
+}
+
+
+pub struct RdxTreeRange<'a, T>
+    where T: Rdx + 'a
+{
+    range: RdxMapRange<'a, T, ()>,
+}
+
+
+impl<'a, T> Iterator for RdxTreeRange<'a, T>
+    where T: Rdx + 'a
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|(x, _)| x)
+    }
+}
+
+
+impl<'a, T> DoubleEndedIterator for RdxTreeRange<'a, T>
+    where T: Rdx + 'a
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|(x, _)| x)
     }
 }
@@ -1,19 +1,13 @@
-use super::Rdx;
+use super::{Digit, Rdx};
 
 impl Rdx for bool {
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        2
-    }
+    const CFG_NBUCKETS: usize = 2;
 
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        1
-    }
+    const CFG_NROUNDS: usize = 1;
 
     #[inline]
-    fn get_bucket(&self, _round: usize) -> usize {
-        if *self { 1 } else { 0 }
+    fn get_bucket(&self, _round: usize) -> Digit<Self> {
+        Digit::new(if *self { 1 } else { 0 })
     }
 
     #[inline]
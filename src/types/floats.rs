@@ -1,42 +1,39 @@
-use super::Rdx;
-
-use std::cmp;
-use std::mem;
+use super::{max, Digit, Rdx};
 
 macro_rules! impl_rdxsort {
     ($t:ty, $alias:ty, $mask:expr) => {
         impl Rdx for $t {
-            #[inline]
-            fn cfg_nbuckets() -> usize {
-                cmp::max(<$alias as Rdx>::cfg_nbuckets(), 2)
-            }
+            const CFG_NBUCKETS: usize = max(<$alias as Rdx>::CFG_NBUCKETS, 2);
 
-            #[inline]
-            fn cfg_nrounds() -> usize {
-                <$alias as Rdx>::cfg_nrounds() + 1
-            }
+            const CFG_NROUNDS: usize = <$alias as Rdx>::CFG_NROUNDS + 1;
 
             #[inline]
-            fn get_bucket(&self, round: usize) -> usize {
-                let alias = unsafe { mem::transmute::<$t, $alias>(*self) };
-                if round < <$alias as Rdx>::cfg_nrounds() {
-                    alias.get_bucket(round)
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                let alias = self.to_bits();
+                if round < <$alias as Rdx>::CFG_NROUNDS {
+                    // flip negative numbers, so their order is inverted without breaking the
+                    // stability of the sort (like reversing the bucket content would do)
+                    if (alias & $mask) == 0 {
+                        alias.get_bucket(round).cast()
+                    } else {
+                        (!alias).get_bucket(round).cast()
+                    }
                 } else {
                     if self.is_nan() {
                         panic!("Sorting of NaNs is not implemented!");
                     } else {
                         if (alias & $mask) == 0 {
-                            1
+                            Digit::new(1)
                         } else {
-                            0
+                            Digit::new(0)
                         }
                     }
                 }
             }
 
             #[inline]
-            fn reverse(round: usize, bucket: usize) -> bool {
-                round == <$alias as Rdx>::cfg_nrounds() && bucket == 0
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
             }
         }
     }
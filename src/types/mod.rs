@@ -2,8 +2,21 @@ mod array;
 mod bool;
 mod char;
 mod floats;
+mod nonzero;
+mod option;
+mod radix;
+mod reverse;
 mod signed_integer;
+mod total;
 mod tuple;
 mod unsigned_integer;
+mod wrapping;
 
-pub use super::Rdx;
+/// `cmp::max` for constant expressions.
+const fn max(a: usize, b: usize) -> usize {
+    if a > b { a } else { b }
+}
+
+pub use super::{Digit, Rdx};
+pub use self::radix::{Radix8, Radix11, Radix16};
+pub use self::total::{NanLast, TotalOrd};
@@ -0,0 +1,37 @@
+use super::{Digit, Rdx};
+
+use core::num::{NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+               NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize};
+
+macro_rules! impl_rdxsort {
+    ($t:ty, $alias:ty) => {
+        impl Rdx for $t {
+            const CFG_NBUCKETS: usize = <$alias as Rdx>::CFG_NBUCKETS;
+
+            const CFG_NROUNDS: usize = <$alias as Rdx>::CFG_NROUNDS;
+
+            #[inline]
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                self.get().get_bucket(round).cast()
+            }
+
+            #[inline]
+            fn reverse(round: usize, bucket: usize) -> bool {
+                <$alias as Rdx>::reverse(round, bucket)
+            }
+        }
+    }
+}
+
+impl_rdxsort!(NonZeroI8, i8);
+impl_rdxsort!(NonZeroI16, i16);
+impl_rdxsort!(NonZeroI32, i32);
+impl_rdxsort!(NonZeroI64, i64);
+impl_rdxsort!(NonZeroI128, i128);
+impl_rdxsort!(NonZeroIsize, isize);
+impl_rdxsort!(NonZeroU8, u8);
+impl_rdxsort!(NonZeroU16, u16);
+impl_rdxsort!(NonZeroU32, u32);
+impl_rdxsort!(NonZeroU64, u64);
+impl_rdxsort!(NonZeroU128, u128);
+impl_rdxsort!(NonZeroUsize, usize);
@@ -0,0 +1,37 @@
+use super::{max, Digit, Rdx};
+
+impl<T> Rdx for Option<T>
+    where T: Rdx
+{
+    const CFG_NBUCKETS: usize = max(T::CFG_NBUCKETS, 2);
+
+    const CFG_NROUNDS: usize = T::CFG_NROUNDS + 1;
+
+    #[inline]
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
+        if round < T::CFG_NROUNDS {
+            match *self {
+                Some(ref x) => x.get_bucket(round).cast(),
+                // use a bucket that is not reversed, so `None`s keep their order
+                None => {
+                    Digit::new((0..T::CFG_NBUCKETS).find(|&b| !T::reverse(round, b)).unwrap_or(0))
+                }
+            }
+        } else {
+            // discriminant, `None` comes first
+            match *self {
+                Some(_) => Digit::new(1),
+                None => Digit::new(0),
+            }
+        }
+    }
+
+    #[inline]
+    fn reverse(round: usize, bucket: usize) -> bool {
+        if round < T::CFG_NROUNDS {
+            T::reverse(round, bucket)
+        } else {
+            false
+        }
+    }
+}
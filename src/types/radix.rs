@@ -0,0 +1,76 @@
+use super::{Digit, Rdx};
+
+/// Sorts the wrapped unsigned integer using digits of 8 bits (256 buckets).
+///
+/// Also wraps byte arrays `[u8; N]`, which are then sorted one byte per round.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Radix8<T>(pub T);
+
+/// Sorts the wrapped unsigned integer using digits of 11 bits (2048 buckets).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Radix11<T>(pub T);
+
+/// Sorts the wrapped unsigned integer using digits of 16 bits (65536 buckets).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Radix16<T>(pub T);
+
+macro_rules! impl_rdxsort {
+    ($wrapper:ident, $bits:expr, $t:ty, $tbits:expr) => {
+        impl Rdx for $wrapper<$t> {
+            const CFG_NBUCKETS: usize = 1 << $bits;
+
+            const CFG_NROUNDS: usize = usize::div_ceil($tbits, $bits);
+
+            #[inline]
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                let shift = round * $bits;
+                Digit::new(((self.0 >> shift) as usize) & ((1 << $bits) - 1))
+            }
+
+            #[inline]
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
+            }
+        }
+    }
+}
+
+macro_rules! impl_rdxsort_all {
+    ($wrapper:ident, $bits:expr) => {
+        impl_rdxsort!($wrapper, $bits, u8, 8);
+        impl_rdxsort!($wrapper, $bits, u16, 16);
+        impl_rdxsort!($wrapper, $bits, u32, 32);
+        impl_rdxsort!($wrapper, $bits, u64, 64);
+        impl_rdxsort!($wrapper, $bits, u128, 128);
+
+
+        #[cfg(target_pointer_width = "16")]
+        impl_rdxsort!($wrapper, $bits, usize, 16);
+
+        #[cfg(target_pointer_width = "32")]
+        impl_rdxsort!($wrapper, $bits, usize, 32);
+
+        #[cfg(target_pointer_width = "64")]
+        impl_rdxsort!($wrapper, $bits, usize, 64);
+    }
+}
+
+impl_rdxsort_all!(Radix8, 8);
+impl_rdxsort_all!(Radix11, 11);
+impl_rdxsort_all!(Radix16, 16);
+
+impl<const N: usize> Rdx for Radix8<[u8; N]> {
+    const CFG_NBUCKETS: usize = 256;
+
+    const CFG_NROUNDS: usize = N;
+
+    #[inline]
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
+        Digit::new(self.0[N - round - 1] as usize)
+    }
+
+    #[inline]
+    fn reverse(_round: usize, _bucket: usize) -> bool {
+        false
+    }
+}
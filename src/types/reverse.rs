@@ -0,0 +1,23 @@
+use super::{Digit, Rdx};
+
+use core::cmp::Reverse;
+
+impl<T> Rdx for Reverse<T>
+    where T: Rdx
+{
+    const CFG_NBUCKETS: usize = T::CFG_NBUCKETS;
+
+    const CFG_NROUNDS: usize = T::CFG_NROUNDS;
+
+    #[inline]
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
+        // mirror buckets, so they are collected in inverted order
+        self.0.get_bucket(round).mirror().cast()
+    }
+
+    #[inline]
+    fn reverse(round: usize, bucket: usize) -> bool {
+        // wrapping types (e.g. tuples) also ask for buckets that `T` never uses
+        bucket < T::CFG_NBUCKETS && T::reverse(round, T::CFG_NBUCKETS - 1 - bucket)
+    }
+}
@@ -1,35 +1,23 @@
-use super::Rdx;
-
-use std::cmp;
-use std::mem;
+use super::{max, Digit, Rdx};
 
 macro_rules! impl_rdxsort {
     ($t:ty, $alias:ty, $min:expr, $zero:expr) => {
         impl Rdx for $t {
-            #[inline]
-            fn cfg_nbuckets() -> usize {
-                cmp::max(<$alias as Rdx>::cfg_nbuckets(), 3)
-            }
+            const CFG_NBUCKETS: usize = max(<$alias as Rdx>::CFG_NBUCKETS, 3);
 
-            #[inline]
-            fn cfg_nrounds() -> usize {
-                <$alias as Rdx>::cfg_nrounds() + 1
-            }
+            const CFG_NROUNDS: usize = <$alias as Rdx>::CFG_NROUNDS + 1;
 
             #[inline]
-            fn get_bucket(&self, round: usize) -> usize {
-                if round < <$alias as Rdx>::cfg_nrounds() {
-                    let alias = unsafe { mem::transmute::<$t, $alias>(*self) };
-                    alias.get_bucket(round)
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                if round < <$alias as Rdx>::CFG_NROUNDS {
+                    let alias = *self as $alias;
+                    alias.get_bucket(round).cast()
+                } else if *self == $min {
+                    Digit::new(0)
+                } else if *self >= $zero {
+                    Digit::new(2)
                 } else {
-                    if *self == $min {
-                        0
-                    } else if *self >= $zero {
-                        2
-                    } else {
-                        1
-                    }
-
+                    Digit::new(1)
                 }
             }
 
@@ -41,10 +29,11 @@ macro_rules! impl_rdxsort {
     }
 }
 
-impl_rdxsort!(i8, u8, i8::min_value(), 0i8);
-impl_rdxsort!(i16, u16, i16::min_value(), 0i16);
-impl_rdxsort!(i32, u32, i32::min_value(), 0i32);
-impl_rdxsort!(i64, u64, i64::min_value(), 0i64);
+impl_rdxsort!(i8, u8, i8::MIN, 0i8);
+impl_rdxsort!(i16, u16, i16::MIN, 0i16);
+impl_rdxsort!(i32, u32, i32::MIN, 0i32);
+impl_rdxsort!(i64, u64, i64::MIN, 0i64);
+impl_rdxsort!(i128, u128, i128::MIN, 0i128);
 
 #[cfg(target_pointer_width = "16")]
 rdx_alias!(isize = i16);
@@ -0,0 +1,114 @@
+use super::{max, Digit, Rdx};
+
+use core::cmp::Ordering;
+
+/// Sorts the wrapped float by its total order, like `total_cmp` does.
+///
+/// In contrast to the plain float implementation, `NaN`s are supported: negative `NaN`s are sorted
+/// before everything else, positive ones after everything else. `-0.0` is sorted before `+0.0`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TotalOrd<T>(pub T);
+
+/// Sorts the wrapped float by its total order, but puts all `NaN`s last, regardless of their sign.
+///
+/// `NaN`s are ordered by their total order among each other.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NanLast<T>(pub T);
+
+macro_rules! impl_rdxsort {
+    ($t:ty, $alias:ty, $mask:expr) => {
+        impl TotalOrd<$t> {
+            /// Maps the float to an unsigned integer with the same (total) order.
+            #[inline]
+            fn key(&self) -> $alias {
+                let alias = self.0.to_bits();
+                if (alias & $mask) == 0 {
+                    alias | $mask
+                } else {
+                    !alias
+                }
+            }
+        }
+
+        impl Rdx for TotalOrd<$t> {
+            const CFG_NBUCKETS: usize = <$alias as Rdx>::CFG_NBUCKETS;
+
+            const CFG_NROUNDS: usize = <$alias as Rdx>::CFG_NROUNDS;
+
+            #[inline]
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                self.key().get_bucket(round).cast()
+            }
+
+            #[inline]
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
+            }
+        }
+
+        impl PartialEq for TotalOrd<$t> {
+            fn eq(&self, other: &TotalOrd<$t>) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for TotalOrd<$t> {}
+
+        impl PartialOrd for TotalOrd<$t> {
+            fn partial_cmp(&self, other: &TotalOrd<$t>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for TotalOrd<$t> {
+            fn cmp(&self, other: &TotalOrd<$t>) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl Rdx for NanLast<$t> {
+            const CFG_NBUCKETS: usize = max(<$alias as Rdx>::CFG_NBUCKETS, 2);
+
+            const CFG_NROUNDS: usize = <$alias as Rdx>::CFG_NROUNDS + 1;
+
+            #[inline]
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                if round < <$alias as Rdx>::CFG_NROUNDS {
+                    TotalOrd(self.0).get_bucket(round).cast()
+                } else if self.0.is_nan() {
+                    Digit::new(1)
+                } else {
+                    Digit::new(0)
+                }
+            }
+
+            #[inline]
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
+            }
+        }
+
+        impl PartialEq for NanLast<$t> {
+            fn eq(&self, other: &NanLast<$t>) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for NanLast<$t> {}
+
+        impl PartialOrd for NanLast<$t> {
+            fn partial_cmp(&self, other: &NanLast<$t>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for NanLast<$t> {
+            fn cmp(&self, other: &NanLast<$t>) -> Ordering {
+                self.0.is_nan().cmp(&other.0.is_nan()).then(self.0.total_cmp(&other.0))
+            }
+        }
+    }
+}
+
+impl_rdxsort!(f32, u32, 0x80000000u32);
+impl_rdxsort!(f64, u64, 0x8000000000000000u64);
@@ -1,20 +1,12 @@
-use super::Rdx;
-
-use std::cmp;
+use super::{max, Digit, Rdx};
 
 impl Rdx for () {
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        0
-    }
+    const CFG_NBUCKETS: usize = 0;
 
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        0
-    }
+    const CFG_NROUNDS: usize = 0;
 
     #[inline]
-    fn get_bucket(&self, _round: usize) -> usize {
+    fn get_bucket(&self, _round: usize) -> Digit<Self> {
         unreachable!()
     }
 
@@ -24,98 +16,61 @@ impl Rdx for () {
     }
 }
 
-impl<A> Rdx for (A,)
-    where A: Rdx
-{
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        A::cfg_nbuckets()
-    }
-
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        A::cfg_nrounds()
-    }
-
-    #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
-        self.0.get_bucket(round)
-    }
-
-    #[inline]
-    fn reverse(round: usize, bucket: usize) -> bool {
-        A::reverse(round, bucket)
-    }
-}
-
-impl<A, B> Rdx for (A, B)
-    where A: Rdx,
-          B: Rdx
-{
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        cmp::max(A::cfg_nbuckets(), B::cfg_nbuckets())
-    }
-
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        A::cfg_nrounds() + B::cfg_nrounds()
-    }
-
-    #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
-        if round < B::cfg_nrounds() {
-            self.1.get_bucket(round)
-        } else {
-            self.0.get_bucket(round - B::cfg_nrounds())
-        }
-    }
-
-    #[inline]
-    fn reverse(round: usize, bucket: usize) -> bool {
-        if round < B::cfg_nrounds() {
-            B::reverse(round, bucket)
-        } else {
-            A::reverse(round - B::cfg_nrounds(), bucket)
+/// Implements `Rdx` for a tuple. The first element is the most significant one, so the other
+/// elements have to be listed in reverse order (least significant first) as well.
+macro_rules! impl_rdxsort {
+    ([$($t:ident),+], [$($idx:tt $rt:ident),*], $idx0:tt $t0:ident) => {
+        impl<$($t),+> Rdx for ($($t,)+)
+            where $($t: Rdx),+
+        {
+            const CFG_NBUCKETS: usize = {
+                let nbuckets = 0;
+                $(
+                    let nbuckets = max(nbuckets, $t::CFG_NBUCKETS);
+                )+
+                nbuckets
+            };
+
+            const CFG_NROUNDS: usize = 0 $( + $t::CFG_NROUNDS )+;
+
+            #[inline]
+            fn get_bucket(&self, round: usize) -> Digit<Self> {
+                $(
+                    if round < $rt::CFG_NROUNDS {
+                        return self.$idx.get_bucket(round).cast();
+                    }
+                    let round = round - $rt::CFG_NROUNDS;
+                )*
+                self.$idx0.get_bucket(round).cast()
+            }
+
+            #[inline]
+            fn reverse(round: usize, bucket: usize) -> bool {
+                $(
+                    if round < $rt::CFG_NROUNDS {
+                        return $rt::reverse(round, bucket);
+                    }
+                    let round = round - $rt::CFG_NROUNDS;
+                )*
+                $t0::reverse(round, bucket)
+            }
         }
     }
 }
 
-impl<A, B, C> Rdx for (A, B, C)
-    where A: Rdx,
-          B: Rdx,
-          C: Rdx
-{
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        cmp::max(A::cfg_nbuckets(),
-                 cmp::max(B::cfg_nbuckets(), C::cfg_nbuckets()))
-    }
-
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        A::cfg_nrounds() + B::cfg_nrounds() + C::cfg_nrounds()
-    }
-
-    #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
-        if round < C::cfg_nrounds() {
-            self.2.get_bucket(round)
-        } else if round < B::cfg_nrounds() + C::cfg_nrounds() {
-            self.1.get_bucket(round - C::cfg_nrounds())
-        } else {
-            self.0.get_bucket(round - B::cfg_nrounds() - C::cfg_nrounds())
-        }
-    }
-
-    #[inline]
-    fn reverse(round: usize, bucket: usize) -> bool {
-        if round < C::cfg_nrounds() {
-            C::reverse(round, bucket)
-        } else if round < B::cfg_nrounds() + C::cfg_nrounds() {
-            B::reverse(round - C::cfg_nrounds(), bucket)
-        } else {
-            A::reverse(round - B::cfg_nrounds() - C::cfg_nrounds(), bucket)
-        }
-    }
-}
+impl_rdxsort!([A], [], 0 A);
+impl_rdxsort!([A, B], [1 B], 0 A);
+impl_rdxsort!([A, B, C], [2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D], [3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E], [4 E, 3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E, F], [5 F, 4 E, 3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E, F, G], [6 G, 5 F, 4 E, 3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E, F, G, H], [7 H, 6 G, 5 F, 4 E, 3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E, F, G, H, I], [8 I, 7 H, 6 G, 5 F, 4 E, 3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E, F, G, H, I, J], [9 J, 8 I, 7 H, 6 G, 5 F, 4 E, 3 D, 2 C, 1 B], 0 A);
+impl_rdxsort!([A, B, C, D, E, F, G, H, I, J, K],
+              [10 K, 9 J, 8 I, 7 H, 6 G, 5 F, 4 E, 3 D, 2 C, 1 B],
+              0 A);
+impl_rdxsort!([A, B, C, D, E, F, G, H, I, J, K, L],
+              [11 L, 10 K, 9 J, 8 I, 7 H, 6 G, 5 F, 4 E, 3 D, 2 C, 1 B],
+              0 A);
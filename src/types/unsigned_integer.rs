@@ -1,20 +1,14 @@
-use super::Rdx;
+use super::{Digit, Rdx};
 
 impl Rdx for u8 {
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        16
-    }
+    const CFG_NBUCKETS: usize = 16;
 
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        2
-    }
+    const CFG_NROUNDS: usize = 2;
 
     #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
         let shift = round << 2;
-        ((self >> shift) & 15u8) as usize
+        Digit::new(((self >> shift) & 15u8) as usize)
     }
 
     #[inline]
@@ -24,20 +18,14 @@ impl Rdx for u8 {
 }
 
 impl Rdx for u16 {
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        16
-    }
+    const CFG_NBUCKETS: usize = 16;
 
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        4
-    }
+    const CFG_NROUNDS: usize = 4;
 
     #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
         let shift = round << 2;
-        ((self >> shift) & 15u16) as usize
+        Digit::new(((self >> shift) & 15u16) as usize)
     }
 
     #[inline]
@@ -47,20 +35,14 @@ impl Rdx for u16 {
 }
 
 impl Rdx for u32 {
-    #[inline]
-    fn cfg_nbuckets() -> usize {
-        16
-    }
+    const CFG_NBUCKETS: usize = 16;
 
-    #[inline]
-    fn cfg_nrounds() -> usize {
-        8
-    }
+    const CFG_NROUNDS: usize = 8;
 
     #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
         let shift = round << 2;
-        ((self >> shift) & 15u32) as usize
+        Digit::new(((self >> shift) & 15u32) as usize)
     }
 
     #[inline]
@@ -70,20 +52,32 @@ impl Rdx for u32 {
 }
 
 impl Rdx for u64 {
+    const CFG_NBUCKETS: usize = 16;
+
+    const CFG_NROUNDS: usize = 16;
+
     #[inline]
-    fn cfg_nbuckets() -> usize {
-        16
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
+        let shift = round << 2;
+        Digit::new(((self >> shift) & 15u64) as usize)
     }
 
     #[inline]
-    fn cfg_nrounds() -> usize {
-        16
+    fn reverse(_round: usize, _bucket: usize) -> bool {
+        false
     }
+}
+
+impl Rdx for u128 {
+    // wider digits, otherwise 32 rounds would be required
+    const CFG_NBUCKETS: usize = 256;
+
+    const CFG_NROUNDS: usize = 16;
 
     #[inline]
-    fn get_bucket(&self, round: usize) -> usize {
-        let shift = round << 2;
-        ((self >> shift) & 15u64) as usize
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
+        let shift = round << 3;
+        Digit::new(((self >> shift) & 255u128) as usize)
     }
 
     #[inline]
@@ -0,0 +1,21 @@
+use super::{Digit, Rdx};
+
+use core::num::Wrapping;
+
+impl<T> Rdx for Wrapping<T>
+    where T: Rdx
+{
+    const CFG_NBUCKETS: usize = T::CFG_NBUCKETS;
+
+    const CFG_NROUNDS: usize = T::CFG_NROUNDS;
+
+    #[inline]
+    fn get_bucket(&self, round: usize) -> Digit<Self> {
+        self.0.get_bucket(round).cast()
+    }
+
+    #[inline]
+    fn reverse(round: usize, bucket: usize) -> bool {
+        T::reverse(round, bucket)
+    }
+}
@@ -0,0 +1,55 @@
+//! Key types that are shared by multiple test files.
+
+// not every test file uses every key type
+#![allow(dead_code)]
+
+use rand::{Rand, Rng};
+
+use rdxsort::{Digit, Rdx};
+
+/// Key type whose buckets only depend on the first field, so the second field tells equal
+/// elements apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tagged(pub u8, pub char);
+
+impl Rdx for Tagged {
+    const CFG_NBUCKETS: usize = <u8 as Rdx>::CFG_NBUCKETS;
+
+    const CFG_NROUNDS: usize = <u8 as Rdx>::CFG_NROUNDS;
+
+    fn get_bucket(&self, round: usize) -> Digit<Tagged> {
+        self.0.get_bucket(round).cast()
+    }
+
+    fn reverse(round: usize, bucket: usize) -> bool {
+        <u8 as Rdx>::reverse(round, bucket)
+    }
+}
+
+/// Reverses the odd buckets of round `2` and the entire data in an extra round.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Odd(pub u16);
+
+impl Rand for Odd {
+    fn rand<R: Rng>(rng: &mut R) -> Odd {
+        Odd(rng.gen())
+    }
+}
+
+impl Rdx for Odd {
+    const CFG_NBUCKETS: usize = 16;
+
+    const CFG_NROUNDS: usize = 5;
+
+    fn get_bucket(&self, round: usize) -> Digit<Odd> {
+        if round < 4 {
+            Digit::new(((self.0 >> (round * 4)) & 15) as usize)
+        } else {
+            Digit::new(0)
+        }
+    }
+
+    fn reverse(round: usize, bucket: usize) -> bool {
+        round == 4 || (round == 2 && bucket % 2 == 1)
+    }
+}
@@ -0,0 +1,219 @@
+extern crate rand;
+extern crate rdxsort;
+
+mod common;
+
+use std::collections::BTreeMap;
+
+use rand::{Rng, XorShiftRng};
+
+use rdxsort::*;
+
+use common::{Odd, Tagged};
+
+#[test]
+fn test_insert() {
+    let mut map: RdxMap<u32, &str> = RdxMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(22, "a"), None);
+    assert_eq!(map.insert(1, "b"), None);
+    assert_eq!(map.insert(1024, "c"), None);
+    assert_eq!(map.insert(22, "d"), Some("a"));
+    assert_eq!(map.len(), 3);
+
+    assert_eq!(map.get(&22), Some(&"d"));
+    assert_eq!(map.get(&23), None);
+    assert_eq!(map.get_key_value(&1), Some((&1, &"b")));
+    assert!(map.contains_key(&1024));
+    assert!(!map.contains_key(&0));
+
+    *map.get_mut(&1).unwrap() = "e";
+    assert!(map.get_mut(&2).is_none());
+
+    let is: Vec<(u32, &str)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(is, vec![(1, "e"), (22, "d"), (1024, "c")]);
+    let keys: Vec<u32> = map.keys().cloned().collect();
+    assert_eq!(keys, vec![1, 22, 1024]);
+    let values: Vec<&str> = map.values().cloned().collect();
+    assert_eq!(values, vec!["e", "d", "c"]);
+}
+
+#[test]
+fn test_insert_keeps_key() {
+    let mut map: RdxMap<Tagged, u32> = RdxMap::new();
+    assert_eq!(map.insert(Tagged(7, 'a'), 1), None);
+
+    // like `BTreeMap`, only the value is replaced
+    assert_eq!(map.insert(Tagged(7, 'b'), 2), Some(1));
+    assert_eq!(map.get_key_value(&Tagged(7, 'x')), Some((&Tagged(7, 'a'), &2)));
+    assert_eq!(map.len(), 1);
+
+    *map.entry(Tagged(7, 'c')).or_insert(0) += 1;
+    assert_eq!(map.get_key_value(&Tagged(7, 'x')), Some((&Tagged(7, 'a'), &3)));
+}
+
+#[test]
+fn test_remove() {
+    let mut map: RdxMap<i16, String> = RdxMap::new();
+    for x in -10..10 {
+        map.insert(x, x.to_string());
+    }
+    assert_eq!(map.remove(&-3), Some("-3".to_string()));
+    assert_eq!(map.remove(&-3), None);
+    assert_eq!(map.remove_entry(&7), Some((7, "7".to_string())));
+    assert_eq!(map.len(), 18);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().count(), 0);
+}
+
+#[test]
+fn test_entry() {
+    let mut map: RdxMap<u8, usize> = RdxMap::new();
+    for &x in &[3u8, 1, 3, 200, 3, 1] {
+        *map.entry(x).or_insert(0) += 1;
+    }
+    let is: Vec<(u8, usize)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(is, vec![(1, 2), (3, 3), (200, 1)]);
+
+    map.entry(1).and_modify(|v| *v = 10).or_default();
+    map.entry(2).and_modify(|v| *v = 10).or_default();
+    assert_eq!(map.get(&1), Some(&10));
+    assert_eq!(map.get(&2), Some(&0));
+    assert_eq!(*map.entry(4).or_insert_with(|| 7), 7);
+
+    match map.entry(3) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(*entry.key(), 3);
+            assert_eq!(*entry.get(), 3);
+            assert_eq!(entry.insert(5), 3);
+            assert_eq!(entry.remove_entry(), (3, 5));
+        }
+        Entry::Vacant(_) => panic!("entry should be occupied"),
+    }
+    match map.entry(3) {
+        Entry::Vacant(entry) => {
+            assert_eq!(*entry.key(), 3);
+            *entry.insert(1) += 1;
+        }
+        Entry::Occupied(_) => panic!("entry should be vacant"),
+    }
+    assert_eq!(map.get(&3), Some(&2));
+    assert_eq!(map.len(), 5);
+}
+
+#[test]
+fn test_rnd() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut map: RdxMap<f32, u64> = RdxMap::new();
+    let mut reference: BTreeMap<u32, (f32, u64)> = BTreeMap::new();
+    for i in 0..10_000u64 {
+        // small set of keys, so some of them collide
+        let k = (rng.gen::<u32>() % 1_000) as f32 - 500f32;
+        assert_eq!(map.insert(k, i), reference.insert(ordered(k), (k, i)).map(|x| x.1));
+    }
+    for _ in 0..1_000 {
+        let k = (rng.gen::<u32>() % 1_000) as f32 - 500f32;
+        assert_eq!(map.remove(&k), reference.remove(&ordered(k)).map(|x| x.1));
+    }
+
+    assert_eq!(map.len(), reference.len());
+    let is: Vec<(f32, u64)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+    let should: Vec<(f32, u64)> = reference.values().cloned().collect();
+    assert_eq!(is, should);
+}
+
+/// Maps floats to integers with the same order, so they can be used as `BTreeMap` keys.
+fn ordered(x: f32) -> u32 {
+    let bits = x.to_bits();
+    if bits >> 31 == 0 {
+        bits | (1 << 31)
+    } else {
+        !bits
+    }
+}
+
+#[test]
+fn test_rnd_reverse() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut map: RdxMap<Odd, ()> = RdxMap::new();
+    let mut data: Vec<Odd> = Vec::new();
+    for _ in 0..1_000 {
+        let x = Odd(rng.gen());
+        if map.insert(x, ()).is_none() {
+            data.push(x);
+        }
+    }
+
+    // the map order must match the sorting order
+    data.rdxsort();
+    let is: Vec<Odd> = map.keys().cloned().collect();
+    assert_eq!(is, data);
+}
+
+#[test]
+fn test_range_reverse() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut map: RdxMap<Odd, usize> = RdxMap::new();
+    for i in 0..1_000 {
+        map.insert(Odd(rng.gen()), i);
+    }
+    let data: Vec<(Odd, usize)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+
+    assert_eq!(map.first_key_value(), data.first().map(|x| (&x.0, &x.1)));
+    assert_eq!(map.last_key_value(), data.last().map(|x| (&x.0, &x.1)));
+
+    // bounds follow the iteration order, which differs from the integer order
+    for _ in 0..100 {
+        let a = rng.gen::<usize>() % data.len();
+        let b = rng.gen::<usize>() % data.len();
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+
+        let is: Vec<(Odd, usize)> = map.range(data[a].0..data[b].0)
+            .map(|(k, v)| (*k, *v))
+            .collect();
+        assert_eq!(is, &data[a..b]);
+        let is: Vec<(Odd, usize)> = map.range(data[a].0..=data[b].0)
+            .rev()
+            .map(|(k, v)| (*k, *v))
+            .collect();
+        let should: Vec<(Odd, usize)> = data[a..=b].iter().rev().cloned().collect();
+        assert_eq!(is, should);
+
+        // keys that are not part of the map, their position is determined by sorting
+        let x = Odd(rng.gen());
+        if map.contains_key(&x) {
+            continue;
+        }
+        let mut keys: Vec<Odd> = data.iter().map(|y| y.0).collect();
+        keys.push(x);
+        keys.rdxsort();
+        let pos = keys.iter().position(|&y| y == x).unwrap();
+        assert_eq!(map.floor_key_value(&x).map(|(k, _)| *k),
+                   if pos > 0 { Some(data[pos - 1].0) } else { None });
+        assert_eq!(map.ceiling_key_value(&x).map(|(k, _)| *k), data.get(pos).map(|y| y.0));
+        let is: Vec<Odd> = map.range(x..).map(|(k, _)| *k).collect();
+        assert_eq!(is, &keys[pos + 1..]);
+    }
+}
+
+#[test]
+fn test_into_iter_reverse() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut map: RdxMap<Odd, usize> = RdxMap::new();
+    for i in 0..1_000 {
+        map.insert(Odd(rng.gen()), i);
+    }
+    let data: Vec<(Odd, usize)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+
+    let mut is: Vec<(Odd, usize)> = map.iter().rev().map(|(k, v)| (*k, *v)).collect();
+    is.reverse();
+    assert_eq!(is, data);
+    assert_eq!(map.keys().len(), data.len());
+    let values: Vec<usize> = map.values().rev().cloned().collect();
+    assert_eq!(values, data.iter().rev().map(|x| x.1).collect::<Vec<usize>>());
+
+    let is: Vec<(Odd, usize)> = map.into_iter().collect();
+    assert_eq!(is, data);
+}
@@ -0,0 +1,336 @@
+#![cfg(feature = "rayon")]
+
+extern crate rand;
+extern crate rayon;
+extern crate rdxsort;
+
+mod common;
+
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use rand::{Rand, Rng, XorShiftRng};
+
+use rdxsort::*;
+
+use common::Odd;
+
+pub const CFG_N: usize = 100_000;
+
+/// Runs `f` using 4 threads, so the data is split into multiple chunks even on machines with few
+/// cores.
+pub fn with_threads<F, R>(f: F) -> R
+    where F: FnOnce() -> R + Send,
+          R: Send
+{
+    rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap().install(f)
+}
+
+pub fn test_generic<T>(data: Vec<T>)
+    where T: Clone + PartialEq + Send,
+          Vec<T>: RdxSort + RdxParSort
+{
+    let mut data = data;
+    let mut data_sorted_ref = data.clone();
+    data_sorted_ref.rdxsort();
+
+    with_threads(|| data.par_rdxsort());
+    assert!(data.len() == data_sorted_ref.len(), "sorted data has wrong lenght!");
+    for (x, y) in data.iter().zip(data_sorted_ref.iter()) {
+        assert!(x == y, "sortd data does not match the reference!");
+    }
+}
+
+pub fn test_rnd_generic<T>()
+    where T: Clone + PartialEq + Rand + Send,
+          Vec<T>: RdxSort + RdxParSort
+{
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<T> = rng.gen_iter::<T>().take(CFG_N).collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_rnd_array4() {
+    test_rnd_generic::<[u8; 4]>();
+}
+
+#[test]
+fn test_rnd_bool() {
+    test_rnd_generic::<bool>();
+}
+
+#[test]
+fn test_rnd_char() {
+    test_rnd_generic::<char>();
+}
+
+#[test]
+fn test_rnd_f32() {
+    test_rnd_generic::<f32>();
+}
+
+#[test]
+fn test_rnd_f64() {
+    test_rnd_generic::<f64>();
+}
+
+#[test]
+fn test_rnd_i8() {
+    test_rnd_generic::<i8>();
+}
+
+#[test]
+fn test_rnd_i16() {
+    test_rnd_generic::<i16>();
+}
+
+#[test]
+fn test_rnd_i32() {
+    test_rnd_generic::<i32>();
+}
+
+#[test]
+fn test_rnd_i64() {
+    test_rnd_generic::<i64>();
+}
+
+#[test]
+fn test_rnd_isize() {
+    test_rnd_generic::<isize>();
+}
+
+#[test]
+fn test_rnd_u8() {
+    test_rnd_generic::<u8>();
+}
+
+#[test]
+fn test_rnd_u16() {
+    test_rnd_generic::<u16>();
+}
+
+#[test]
+fn test_rnd_u32() {
+    test_rnd_generic::<u32>();
+}
+
+#[test]
+fn test_rnd_u64() {
+    test_rnd_generic::<u64>();
+}
+
+#[test]
+fn test_rnd_usize() {
+    test_rnd_generic::<usize>();
+}
+
+#[test]
+fn test_rnd_tuple2() {
+    test_rnd_generic::<(u32, u64)>();
+}
+
+#[test]
+fn test_rnd_tuple3() {
+    test_rnd_generic::<(u8, i32, char)>();
+}
+
+#[test]
+fn test_rnd_reverse() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<std::cmp::Reverse<i32>> = rng.gen_iter::<i32>()
+        .take(CFG_N)
+        .map(std::cmp::Reverse)
+        .collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_small_u64() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<u64> = rng.gen_iter::<u64>().take(CFG_N).map(|x| x % 1000).collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_skewed_u32() {
+    // most elements share the most significant digit, so their bucket gets split again
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<u32> = rng.gen_iter::<u32>()
+        .take(CFG_N)
+        .enumerate()
+        .map(|(i, x)| if i % 10 == 0 { x } else { x >> 4 })
+        .collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_small_i64() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<i64> = rng.gen_iter::<i64>().take(CFG_N).map(|x| x % 1000).collect();
+    test_generic(data);
+}
+
+#[test]
+fn test_constant_u32() {
+    test_generic(vec![42u32; CFG_N]);
+}
+
+#[test]
+fn test_single_u32() {
+    test_generic(vec![42u32]);
+}
+
+#[test]
+fn test_rnd_custom() {
+    test_rnd_generic::<Odd>();
+}
+
+static FLAKY_FLIPPED: AtomicBool = AtomicBool::new(false);
+
+/// Key `1` goes to bucket `1` once and to bucket `0` afterwards, all other keys go to bucket `0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Flaky(u32);
+
+impl Rdx for Flaky {
+    const CFG_NBUCKETS: usize = 2;
+
+    const CFG_NROUNDS: usize = 1;
+
+    fn get_bucket(&self, _round: usize) -> Digit<Flaky> {
+        if self.0 == 1 && !FLAKY_FLIPPED.swap(true, Ordering::SeqCst) {
+            Digit::new(1)
+        } else {
+            Digit::new(0)
+        }
+    }
+
+    fn reverse(_round: usize, _bucket: usize) -> bool {
+        false
+    }
+}
+
+#[test]
+fn test_inconsistent() {
+    // the changing element is part of the first chunk, so it would end up in the slots of the
+    // second chunk
+    let mut data = vec![Flaky(0); CFG_N];
+    data[0] = Flaky(1);
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        with_threads(|| data.par_rdxsort())
+    }));
+    assert!(result.is_err());
+
+    let mut keys: Vec<u32> = data.iter().map(|x| x.0).collect();
+    keys.sort();
+    assert_eq!(keys[CFG_N - 1], 1);
+    assert_eq!(keys[CFG_N - 2], 0);
+}
+
+/// Neither `Clone` nor `Copy`, counts how often it is dropped.
+struct Tracked {
+    key: u32,
+    poisoned: bool,
+    flaky: bool,
+    calls: AtomicUsize,
+    drops: Arc<AtomicUsize>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Rdx for Tracked {
+    const CFG_NBUCKETS: usize = u32::CFG_NBUCKETS;
+
+    const CFG_NROUNDS: usize = u32::CFG_NROUNDS;
+
+    fn get_bucket(&self, round: usize) -> Digit<Tracked> {
+        // inject a panic into the scatter of the second round that is sorted per bucket
+        let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        assert!(!self.poisoned || calls < 7, "poisoned element");
+        let bucket = self.key.get_bucket(round).get();
+        if self.flaky && calls > 1 {
+            // different bucket after the histogram prepass
+            Digit::new((bucket + 1) % u32::CFG_NBUCKETS)
+        } else {
+            Digit::new(bucket)
+        }
+    }
+
+    fn reverse(round: usize, bucket: usize) -> bool {
+        u32::reverse(round, bucket)
+    }
+}
+
+fn gen_tracked(drops: &Arc<AtomicUsize>) -> Vec<Tracked> {
+    let mut rng = XorShiftRng::new_unseeded();
+    rng.gen_iter::<u32>()
+        .take(CFG_N)
+        .map(|key| {
+            Tracked {
+                key,
+                poisoned: false,
+                flaky: false,
+                calls: AtomicUsize::new(0),
+                drops: drops.clone(),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_rnd_move() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let mut data = gen_tracked(&drops);
+    let mut reference: Vec<u32> = data.iter().map(|x| x.key).collect();
+    reference.rdxsort();
+
+    with_threads(|| data.par_rdxsort());
+    assert!(data.iter().map(|x| x.key).eq(reference.into_iter()));
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(data);
+    assert_eq!(drops.load(Ordering::SeqCst), CFG_N);
+}
+
+#[test]
+fn test_panic_move() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let mut data = gen_tracked(&drops);
+    data[CFG_N / 2].poisoned = true;
+    let mut reference: Vec<u32> = data.iter().map(|x| x.key).collect();
+    reference.sort();
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        with_threads(|| data.par_rdxsort())
+    }));
+    assert!(result.is_err());
+
+    // every element is still there exactly once
+    let mut keys: Vec<u32> = data.iter().map(|x| x.key).collect();
+    keys.sort();
+    assert!(keys == reference);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(data);
+    assert_eq!(drops.load(Ordering::SeqCst), CFG_N);
+}
+
+#[test]
+fn test_inconsistent_move() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let mut data = gen_tracked(&drops);
+    data[0].flaky = true;
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        with_threads(|| data.par_rdxsort())
+    }));
+    assert!(result.is_err());
+    assert_eq!(data.len(), CFG_N);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(data);
+    assert_eq!(drops.load(Ordering::SeqCst), CFG_N);
+}
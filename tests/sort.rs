@@ -1,6 +1,12 @@
+// the tests predate the associated `MIN`/`MAX` constants
+#![allow(clippy::legacy_numeric_constants)]
+
 extern crate rand;
 extern crate rdxsort;
 
+mod common;
+
+use std::cmp::Reverse;
 use std::collections;
 use std::collections::hash_map::RandomState;
 use std::hash::{BuildHasher, Hash, Hasher};
@@ -12,8 +18,11 @@ use rand::{Rand, Rng, XorShiftRng};
 
 use rdxsort::*;
 
+use common::Odd;
+
 pub const CFG_N: usize = 10_000;
 pub const CFG_M: usize = 10;
+pub const CFG_N_SMALL: usize = 1_000;
 
 fn is_sorted<T>(data: &[T]) -> bool
     where T: Clone,
@@ -56,11 +65,13 @@ trivial_myhash!(i8);
 trivial_myhash!(i16);
 trivial_myhash!(i32);
 trivial_myhash!(i64);
+trivial_myhash!(i128);
 trivial_myhash!(isize);
 trivial_myhash!(u8);
 trivial_myhash!(u16);
 trivial_myhash!(u32);
 trivial_myhash!(u64);
+trivial_myhash!(u128);
 trivial_myhash!(usize);
 
 impl MyHash for f32 {
@@ -117,17 +128,33 @@ pub fn test_empty_generic<T>()
     test_generic(data);
 }
 
-pub fn test_rnd_generic<T>(vspecial: Vec<T>)
-    where T: Clone + PartialOrd + Rand + MyHash,
-          Vec<T>: RdxSort
+fn gen_rnd_generic<T>(vspecial: Vec<T>) -> Vec<T>
+    where T: Clone + Rand + MyHash
+{
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<T> = rng.gen_iter::<T>().take(CFG_N).collect();
+    mix_special_generic(&mut rng, data, vspecial)
+}
+
+/// Same as `gen_rnd_generic`, but `rand` does not support `u128`.
+fn gen_rnd_u128(vspecial: Vec<u128>) -> Vec<u128> {
+    let mut rng = XorShiftRng::new_unseeded();
+    let data: Vec<u128> = rng.gen_iter::<(u64, u64)>()
+        .take(CFG_N)
+        .map(|(hi, lo)| ((hi as u128) << 64) | (lo as u128))
+        .collect();
+    mix_special_generic(&mut rng, data, vspecial)
+}
+
+/// Places every special value `CFG_M` times at random positions of `data`.
+fn mix_special_generic<T>(rng: &mut XorShiftRng, data: Vec<T>, vspecial: Vec<T>) -> Vec<T>
+    where T: Clone + MyHash
 {
     // config
     let entropy_threshold = 0.5f64;
 
-    // generate data
-    let mut rng = XorShiftRng::new_unseeded();
-    let mut data: Vec<T> = rng.gen_iter::<T>().take(CFG_N).collect();
-    let mut positions: Vec<usize> = (0..(CFG_N + 1)).collect();
+    let mut data = data;
+    let mut positions: Vec<usize> = (0..CFG_N).collect();
     rng.shuffle(&mut positions[..]);
     assert!(vspecial.len() * CFG_M < CFG_N, "to many special values to test!");
     for (i, x) in vspecial.into_iter().enumerate() {
@@ -139,7 +166,61 @@ pub fn test_rnd_generic<T>(vspecial: Vec<T>)
     assert!(data.len() == CFG_N, "generated data has wrong length!");
     assert!(guess_entropy(&data) >= entropy_threshold, "generated data does not contain enough entropy!");
 
-    test_generic(data);
+    data
+}
+
+pub fn test_rnd_generic<T>(vspecial: Vec<T>)
+    where T: Clone + PartialOrd + Rand + MyHash,
+          Vec<T>: RdxSort
+{
+    test_generic(gen_rnd_generic(vspecial));
+}
+
+pub fn test_desc_generic<T>(data: Vec<T>)
+    where T: Clone + PartialOrd,
+          Vec<T>: RdxSort
+{
+    let mut data = data;
+    let n = data.len();
+    let mut data_sorted_ref = data.clone();
+    data_sorted_ref.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    data.rdxsort_desc();
+    assert!(data.len() == n, "sorted data has wrong lenght!");
+    for (x, y) in data.iter().zip(data_sorted_ref.iter()) {
+        assert!(x == y, "sortd data does not match the reference!");
+    }
+}
+
+pub fn test_rnd_desc_generic<T>(vspecial: Vec<T>)
+    where T: Clone + PartialOrd + Rand + MyHash,
+          Vec<T>: RdxSort
+{
+    test_desc_generic(gen_rnd_generic(vspecial));
+}
+
+pub fn test_unstable_generic<T>(data: Vec<T>)
+    where T: Clone + PartialOrd,
+          Vec<T>: RdxSort
+{
+    let mut data = data;
+    let n = data.len();
+    let mut data_sorted_ref = data.clone();
+    data_sorted_ref.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    data.rdxsort_unstable();
+    assert!(data.len() == n, "sorted data has wrong lenght!");
+    assert!(is_sorted(&data), "data is not sorted!");
+    for (x, y) in data.iter().zip(data_sorted_ref.iter()) {
+        assert!(x == y, "sortd data does not match the reference!");
+    }
+}
+
+pub fn test_rnd_unstable_generic<T>(vspecial: Vec<T>)
+    where T: Clone + PartialOrd + Rand + MyHash,
+          Vec<T>: RdxSort
+{
+    test_unstable_generic(gen_rnd_generic(vspecial));
 }
 
 pub fn test_single_generic<T>(x: T)
@@ -184,6 +265,16 @@ mod sub_array {
         test_rnd_generic::<[u8; 4]>(vec![]);
     }
 
+    #[test]
+    fn test_rnd_desc_array4() {
+        test_rnd_desc_generic::<[u8; 4]>(vec![]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_array4() {
+        test_rnd_unstable_generic::<[u8; 4]>(vec![]);
+    }
+
     #[test]
     fn test_empty_array4() {
         test_empty_generic::<[u8; 4]>();
@@ -193,6 +284,60 @@ mod sub_array {
     fn test_single_array4() {
         test_single_generic::<[u8; 4]>([1, 2, 3, 4]);
     }
+
+    /// Arrays above the size that `rand` supports, with few distinct values per position.
+    fn gen_rnd_array_generic<T, F, const N: usize>(f: F) -> Vec<[T; N]>
+        where T: Copy + Default,
+              F: Fn(&mut XorShiftRng) -> T
+    {
+        let mut rng = XorShiftRng::new_unseeded();
+        (0..CFG_N)
+            .map(|_| {
+                let mut x = [T::default(); N];
+                for y in x.iter_mut() {
+                    *y = f(&mut rng);
+                }
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rnd_array48() {
+        let data = gen_rnd_array_generic::<u8, _, 48>(|rng| rng.gen_range(0, 2));
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_array64() {
+        let data = gen_rnd_array_generic::<u8, _, 64>(|rng| rng.gen());
+        test_generic(data.clone());
+        test_desc_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_array33_i16() {
+        let data = gen_rnd_array_generic::<i16, _, 33>(|rng| rng.gen_range(-1, 2));
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_array40_f32() {
+        let data = gen_rnd_array_generic::<f32, _, 40>(|rng| *rng.choose(&[-1f32, 0f32, 1f32]).unwrap());
+        test_generic(data);
+    }
+
+    #[test]
+    fn test_rounds_array64() {
+        assert_eq!(<[u8; 64]>::CFG_NBUCKETS, 16);
+        assert_eq!(<[u8; 64]>::CFG_NROUNDS, 128);
+        assert_eq!(<Radix8<[u8; 64]>>::CFG_NBUCKETS, 256);
+        assert_eq!(<Radix8<[u8; 64]>>::CFG_NROUNDS, 64);
+    }
 }
 
 mod sub_bool {
@@ -203,6 +348,16 @@ mod sub_bool {
         test_rnd_generic::<bool>(vec![false, true]);
     }
 
+    #[test]
+    fn test_rnd_desc_bool() {
+        test_rnd_desc_generic::<bool>(vec![false, true]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_bool() {
+        test_rnd_unstable_generic::<bool>(vec![false, true]);
+    }
+
     #[test]
     fn test_empty_bool() {
         test_empty_generic::<bool>();
@@ -224,6 +379,16 @@ mod sub_char {
         test_rnd_generic::<char>(vec!['\0', char::MAX]);
     }
 
+    #[test]
+    fn test_rnd_desc_char() {
+        test_rnd_desc_generic::<char>(vec!['\0', char::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_char() {
+        test_rnd_unstable_generic::<char>(vec!['\0', char::MAX]);
+    }
+
     #[test]
     fn test_empty_char() {
         test_empty_generic::<char>();
@@ -243,6 +408,16 @@ mod sub_i8 {
         test_rnd_generic::<i8>(vec![i8::min_value(), i8::min_value() + 1, -1i8, 0i8, 1i8, i8::max_value() - 1, i8::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_i8() {
+        test_rnd_desc_generic::<i8>(vec![i8::MIN, i8::MIN + 1, -1i8, 0i8, 1i8, i8::MAX - 1, i8::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_i8() {
+        test_rnd_unstable_generic::<i8>(vec![i8::MIN, i8::MIN + 1, -1i8, 0i8, 1i8, i8::MAX - 1, i8::MAX]);
+    }
+
     #[test]
     fn test_full_i8() {
         test_full_generic::<i8>(i8::min_value(), i8::max_value());
@@ -267,6 +442,16 @@ mod sub_i16 {
         test_rnd_generic::<i16>(vec![i16::min_value(), i16::min_value() + 1, -1i16, 0i16, 1i16, i16::max_value() - 1, i16::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_i16() {
+        test_rnd_desc_generic::<i16>(vec![i16::MIN, i16::MIN + 1, -1i16, 0i16, 1i16, i16::MAX - 1, i16::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_i16() {
+        test_rnd_unstable_generic::<i16>(vec![i16::MIN, i16::MIN + 1, -1i16, 0i16, 1i16, i16::MAX - 1, i16::MAX]);
+    }
+
     #[test]
     fn test_full_i16() {
         test_full_generic::<i16>(i16::min_value(), i16::max_value());
@@ -291,6 +476,16 @@ mod sub_i32 {
         test_rnd_generic::<i32>(vec![i32::min_value(), i32::min_value() + 1, -1i32, 0i32, 1i32, i32::max_value() - 1, i32::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_i32() {
+        test_rnd_desc_generic::<i32>(vec![i32::MIN, i32::MIN + 1, -1i32, 0i32, 1i32, i32::MAX - 1, i32::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_i32() {
+        test_rnd_unstable_generic::<i32>(vec![i32::MIN, i32::MIN + 1, -1i32, 0i32, 1i32, i32::MAX - 1, i32::MAX]);
+    }
+
     #[test]
     fn test_empty_i32() {
         test_empty_generic::<i32>();
@@ -310,6 +505,16 @@ mod sub_i64 {
         test_rnd_generic::<i64>(vec![i64::min_value(), i64::min_value() + 1, -1i64, 0i64, 1i64, i64::max_value() - 1, i64::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_i64() {
+        test_rnd_desc_generic::<i64>(vec![i64::MIN, i64::MIN + 1, -1i64, 0i64, 1i64, i64::MAX - 1, i64::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_i64() {
+        test_rnd_unstable_generic::<i64>(vec![i64::MIN, i64::MIN + 1, -1i64, 0i64, 1i64, i64::MAX - 1, i64::MAX]);
+    }
+
     #[test]
     fn test_empty_i64() {
         test_empty_generic::<i64>();
@@ -319,6 +524,58 @@ mod sub_i64 {
     fn test_single_i64() {
         test_single_generic::<i64>(3i64);
     }
+
+    #[test]
+    fn test_rnd_small_i64() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<i64> = rng.gen_iter::<i64>().take(CFG_N).map(|x| x % 1000).collect();
+        test_generic(data);
+
+        let data: Vec<i64> = rng.gen_iter::<i64>().take(CFG_N).map(|x| -(x % 1000).abs() - 1).collect();
+        test_generic(data);
+    }
+
+}
+
+mod sub_i128 {
+    use super::*;
+
+    fn gen_rnd_i128(vspecial: Vec<i128>) -> Vec<i128> {
+        let data = gen_rnd_u128(vec![]).into_iter().map(|x| x as i128).collect();
+        let mut rng = XorShiftRng::new_unseeded();
+        mix_special_generic(&mut rng, data, vspecial)
+    }
+
+    #[test]
+    fn test_rnd_i128() {
+        test_generic(gen_rnd_i128(vec![i128::MIN, i128::MIN + 1, -1i128, 0i128, 1i128, i128::MAX - 1, i128::MAX]));
+    }
+
+    #[test]
+    fn test_rnd_desc_i128() {
+        test_desc_generic(gen_rnd_i128(vec![i128::MIN, i128::MIN + 1, -1i128, 0i128, 1i128, i128::MAX - 1, i128::MAX]));
+    }
+
+    #[test]
+    fn test_rnd_unstable_i128() {
+        test_unstable_generic(gen_rnd_i128(vec![i128::MIN, i128::MIN + 1, -1i128, 0i128, 1i128, i128::MAX - 1, i128::MAX]));
+    }
+
+    #[test]
+    fn test_empty_i128() {
+        test_empty_generic::<i128>();
+    }
+
+    #[test]
+    fn test_single_i128() {
+        test_single_generic::<i128>(3i128);
+    }
+
+    #[test]
+    fn test_rnd_small_i128() {
+        let data: Vec<i128> = gen_rnd_i128(vec![]).into_iter().map(|x| x % 1000).collect();
+        test_generic(data);
+    }
 }
 
 mod sub_isize {
@@ -329,6 +586,16 @@ mod sub_isize {
         test_rnd_generic::<isize>(vec![isize::min_value(), isize::min_value() + 1, -1, 0, 1, isize::max_value() - 1, isize::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_isize() {
+        test_rnd_desc_generic::<isize>(vec![isize::MIN, isize::MIN + 1, -1, 0, 1, isize::MAX - 1, isize::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_isize() {
+        test_rnd_unstable_generic::<isize>(vec![isize::MIN, isize::MIN + 1, -1, 0, 1, isize::MAX - 1, isize::MAX]);
+    }
+
     #[test]
     fn test_empty_isize() {
         test_empty_generic::<isize>();
@@ -348,6 +615,16 @@ mod sub_u8 {
         test_rnd_generic::<u8>(vec![0u8, 1u8, u8::max_value() - 1, u8::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_u8() {
+        test_rnd_desc_generic::<u8>(vec![0u8, 1u8, u8::MAX - 1, u8::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_u8() {
+        test_rnd_unstable_generic::<u8>(vec![0u8, 1u8, u8::MAX - 1, u8::MAX]);
+    }
+
     #[test]
     fn test_full_u8() {
         test_full_generic::<u8>(u8::min_value(), u8::max_value());
@@ -372,6 +649,16 @@ mod sub_u16 {
         test_rnd_generic::<u16>(vec![0u16, 1u16, u16::max_value() - 1, u16::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_u16() {
+        test_rnd_desc_generic::<u16>(vec![0u16, 1u16, u16::MAX - 1, u16::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_u16() {
+        test_rnd_unstable_generic::<u16>(vec![0u16, 1u16, u16::MAX - 1, u16::MAX]);
+    }
+
     #[test]
     fn test_full_u16() {
         test_full_generic::<u16>(u16::min_value(), u16::max_value());
@@ -396,6 +683,16 @@ mod sub_u32 {
         test_rnd_generic::<u32>(vec![0u32, 1u32, u32::max_value() - 1, u32::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_u32() {
+        test_rnd_desc_generic::<u32>(vec![0u32, 1u32, u32::MAX - 1, u32::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_u32() {
+        test_rnd_unstable_generic::<u32>(vec![0u32, 1u32, u32::MAX - 1, u32::MAX]);
+    }
+
     #[test]
     fn test_empty_u32() {
         test_empty_generic::<u32>();
@@ -415,6 +712,16 @@ mod sub_u64 {
         test_rnd_generic::<u64>(vec![0u64, 1u64, u64::max_value() - 1, u64::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_u64() {
+        test_rnd_desc_generic::<u64>(vec![0u64, 1u64, u64::MAX - 1, u64::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_u64() {
+        test_rnd_unstable_generic::<u64>(vec![0u64, 1u64, u64::MAX - 1, u64::MAX]);
+    }
+
     #[test]
     fn test_empty_u64() {
         test_empty_generic::<u64>();
@@ -424,6 +731,58 @@ mod sub_u64 {
     fn test_single_u64() {
         test_single_generic::<u64>(3u64);
     }
+
+    #[test]
+    fn test_rnd_small_u64() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<u64> = rng.gen_iter::<u64>().take(CFG_N).map(|x| x % 1000).collect();
+        test_generic(data);
+    }
+
+}
+
+mod sub_u128 {
+    use super::*;
+
+    #[test]
+    fn test_rnd_u128() {
+        test_generic(gen_rnd_u128(vec![u128::MIN, 1u128, u128::MAX - 1, u128::MAX]));
+    }
+
+    #[test]
+    fn test_rnd_desc_u128() {
+        test_desc_generic(gen_rnd_u128(vec![u128::MIN, 1u128, u128::MAX - 1, u128::MAX]));
+    }
+
+    #[test]
+    fn test_rnd_unstable_u128() {
+        test_unstable_generic(gen_rnd_u128(vec![u128::MIN, 1u128, u128::MAX - 1, u128::MAX]));
+    }
+
+    #[test]
+    fn test_empty_u128() {
+        test_empty_generic::<u128>();
+    }
+
+    #[test]
+    fn test_single_u128() {
+        test_single_generic::<u128>(3u128);
+    }
+
+    #[test]
+    fn test_rnd_small_u128() {
+        let data: Vec<u128> = gen_rnd_u128(vec![]).into_iter().map(|x| x % 1000).collect();
+        test_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_radix16_u128() {
+        let mut data = gen_rnd_u128(vec![]);
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort();
+        data.rdxsort_by_key(|&x| Radix16(x));
+        assert!(data == data_sorted_ref, "sortd data does not match the reference!");
+    }
 }
 
 mod sub_usize {
@@ -434,6 +793,16 @@ mod sub_usize {
         test_rnd_generic::<usize>(vec![0, 1, usize::max_value() - 1, usize::max_value()]);
     }
 
+    #[test]
+    fn test_rnd_desc_usize() {
+        test_rnd_desc_generic::<usize>(vec![0, 1, usize::MAX - 1, usize::MAX]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_usize() {
+        test_rnd_unstable_generic::<usize>(vec![0, 1, usize::MAX - 1, usize::MAX]);
+    }
+
     #[test]
     fn test_empty_usize() {
         test_empty_generic::<usize>();
@@ -455,6 +824,16 @@ mod sub_f32 {
         test_rnd_generic::<f32>(vec![-f32::INFINITY, -1.0e-40_f32, -1.0e-41_f32, -0f32, 0f32, 1.0e-41_f32, 1.0e-40_f32, f32::INFINITY]);
     }
 
+    #[test]
+    fn test_rnd_desc_f32() {
+        test_rnd_desc_generic::<f32>(vec![-f32::INFINITY, -1.0e-40_f32, -1.0e-41_f32, -0f32, 0f32, 1.0e-41_f32, 1.0e-40_f32, f32::INFINITY]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_f32() {
+        test_rnd_unstable_generic::<f32>(vec![-f32::INFINITY, -1.0e-40_f32, -1.0e-41_f32, -0f32, 0f32, 1.0e-41_f32, 1.0e-40_f32, f32::INFINITY]);
+    }
+
     #[test]
     fn test_empty_f32() {
         test_empty_generic::<f32>();
@@ -476,6 +855,16 @@ mod sub_f64 {
         test_rnd_generic::<f64>(vec![-f64::INFINITY, -1.0e-308_f64, -1.0e-309_f64, -0f64, 0f64, 1.0e-309_f64, 1.0e-308_f64, f64::INFINITY]);
     }
 
+    #[test]
+    fn test_rnd_desc_f64() {
+        test_rnd_desc_generic::<f64>(vec![-f64::INFINITY, -1.0e-308_f64, -1.0e-309_f64, -0f64, 0f64, 1.0e-309_f64, 1.0e-308_f64, f64::INFINITY]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_f64() {
+        test_rnd_unstable_generic::<f64>(vec![-f64::INFINITY, -1.0e-308_f64, -1.0e-309_f64, -0f64, 0f64, 1.0e-309_f64, 1.0e-308_f64, f64::INFINITY]);
+    }
+
     #[test]
     fn test_empty_f64() {
         test_empty_generic::<f64>();
@@ -485,6 +874,14 @@ mod sub_f64 {
     fn test_single_f64() {
         test_single_generic::<f64>(3f64);
     }
+
+    #[test]
+    fn test_rnd_negative_f64() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<f64> = rng.gen_iter::<f64>().take(CFG_N).map(|x| -x).collect();
+        test_generic(data);
+    }
+
 }
 
 mod sub_tuple {
@@ -505,6 +902,16 @@ mod sub_tuple {
         test_rnd_generic::<(u8,)>(vec![]);
     }
 
+    #[test]
+    fn test_rnd_desc_tuple1() {
+        test_rnd_desc_generic::<(u8,)>(vec![]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_tuple1() {
+        test_rnd_unstable_generic::<(u8,)>(vec![]);
+    }
+
     #[test]
     fn test_empty_tuple1() {
         test_empty_generic::<(u8,)>();
@@ -520,6 +927,16 @@ mod sub_tuple {
         test_rnd_generic::<(u8, i32)>(vec![]);
     }
 
+    #[test]
+    fn test_rnd_desc_tuple2() {
+        test_rnd_desc_generic::<(u8, i32)>(vec![]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_tuple2() {
+        test_rnd_unstable_generic::<(u8, i32)>(vec![]);
+    }
+
     #[test]
     fn test_empty_tuple2() {
         test_empty_generic::<(u8, i32)>();
@@ -535,6 +952,16 @@ mod sub_tuple {
         test_rnd_generic::<(u8, i32, char)>(vec![]);
     }
 
+    #[test]
+    fn test_rnd_desc_tuple3() {
+        test_rnd_desc_generic::<(u8, i32, char)>(vec![]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_tuple3() {
+        test_rnd_unstable_generic::<(u8, i32, char)>(vec![]);
+    }
+
     #[test]
     fn test_empty_tuple3() {
         test_empty_generic::<(u8, i32, char)>();
@@ -544,4 +971,1091 @@ mod sub_tuple {
     fn test_single_tuple3() {
         test_single_generic::<(u8, i32, char)>((1u8, 1337i32, 'x'));
     }
+
+    /// Small value ranges, so that the later elements are actually needed to break ties.
+    fn gen_rnd_tuple_generic<T, F>(f: F) -> Vec<T>
+        where F: Fn(&mut XorShiftRng) -> T
+    {
+        let mut rng = XorShiftRng::new_unseeded();
+        (0..CFG_N).map(|_| f(&mut rng)).collect()
+    }
+
+    fn test_rnd_tuple_generic<T>(data: Vec<T>)
+        where T: Clone + PartialOrd,
+              Vec<T>: RdxSort
+    {
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_tuple_reverse() {
+        // `Reverse<bool>` uses less buckets than the tuple, which forwards all of them
+        let data = gen_rnd_tuple_generic(|rng| (Reverse(rng.gen::<bool>()), rng.gen::<u8>()));
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+
+        let mut tree: RdxTree<(Reverse<bool>, u8)> = RdxTree::new();
+        for &x in &data {
+            tree.insert(x);
+        }
+        let reference: collections::BTreeSet<(Reverse<bool>, u8)> = data.into_iter().collect();
+        let is: Vec<(Reverse<bool>, u8)> = tree.into_iter().collect();
+        let should: Vec<(Reverse<bool>, u8)> = reference.into_iter().collect();
+        assert_eq!(is, should);
+    }
+
+    #[test]
+    fn test_rnd_tuple4() {
+        test_rnd_tuple_generic(gen_rnd_tuple_generic(|rng| {
+            (rng.gen_range(0u16, 3), rng.gen_range(-2i32, 2), rng.gen::<bool>(), rng.gen::<u64>())
+        }));
+    }
+
+    #[test]
+    fn test_rnd_tuple5() {
+        test_rnd_tuple_generic(gen_rnd_tuple_generic(|rng| {
+            (rng.gen_range(0u8, 2),
+             rng.gen_range(-3i16, 3),
+             rng.gen_range(0u32, 2),
+             rng.gen_range(-1f32, 1f32),
+             rng.gen::<i8>())
+        }));
+    }
+
+    #[test]
+    fn test_rnd_tuple6() {
+        test_rnd_tuple_generic(gen_rnd_tuple_generic(|rng| {
+            (rng.gen_range(-1i64, 1),
+             rng.gen::<bool>(),
+             rng.gen_range(0usize, 3),
+             *rng.choose(&[-0.5f64, 0f64, 0.5f64]).unwrap(),
+             rng.gen_range(-2isize, 2),
+             rng.gen::<char>())
+        }));
+    }
+
+    #[test]
+    fn test_rnd_tuple12() {
+        test_rnd_tuple_generic(gen_rnd_tuple_generic(|rng| {
+            (rng.gen::<bool>(),
+             rng.gen_range(-1i8, 1),
+             rng.gen_range(0u8, 2),
+             rng.gen_range(-1i16, 1),
+             *rng.choose(&[-1f32, 1f32]).unwrap(),
+             rng.gen_range(0u16, 2),
+             rng.gen_range(-1i32, 1),
+             rng.gen::<bool>(),
+             rng.gen_range(0u32, 2),
+             *rng.choose(&[-1f64, 0f64]).unwrap(),
+             rng.gen_range(-1i64, 1),
+             rng.gen::<u64>())
+        }));
+    }
+
+    #[test]
+    fn test_empty_tuple12() {
+        test_empty_generic::<(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64, bool, char)>();
+    }
+
+    #[test]
+    fn test_single_tuple12() {
+        test_single_generic::<(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64, bool, char)>(
+            (1u8, -1i8, 2u16, -2i16, 3u32, -3i32, 4u64, -4i64, 0.5f32, -0.5f64, true, 'x'));
+    }
+}
+
+mod sub_nonzero {
+    use super::*;
+
+    use std::num::{NonZeroI32, NonZeroU64};
+
+    #[test]
+    fn test_rnd_nonzero_u64() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<NonZeroU64> = rng.gen_iter::<u64>()
+            .take(CFG_N)
+            .filter_map(NonZeroU64::new)
+            .chain(vec![NonZeroU64::new(1).unwrap(), NonZeroU64::new(u64::MAX).unwrap()])
+            .collect();
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_nonzero_i32() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<NonZeroI32> = rng.gen_iter::<i32>()
+            .take(CFG_N)
+            .filter_map(NonZeroI32::new)
+            .chain(vec![NonZeroI32::new(i32::MIN).unwrap(), NonZeroI32::new(-1).unwrap(), NonZeroI32::new(1).unwrap()])
+            .collect();
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_empty_nonzero_u64() {
+        test_empty_generic::<NonZeroU64>();
+    }
+}
+
+mod sub_wrapping {
+    use super::*;
+
+    use std::num::Wrapping;
+
+    #[test]
+    fn test_rnd_wrapping_i16() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<Wrapping<i16>> = rng.gen_iter::<i16>().take(CFG_N).map(Wrapping).collect();
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_empty_wrapping_i16() {
+        test_empty_generic::<Wrapping<i16>>();
+    }
+}
+
+mod sub_option {
+    use super::*;
+
+    fn gen_rnd_option<T>() -> Vec<Option<T>>
+        where T: Rand
+    {
+        let mut rng = XorShiftRng::new_unseeded();
+        (0..CFG_N)
+            .map(|i| if i % 5 == 0 { None } else { Some(rng.gen()) })
+            .collect()
+    }
+
+    #[test]
+    fn test_rnd_option_u32() {
+        let data = gen_rnd_option::<u32>();
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_option_f32() {
+        let data: Vec<Option<f32>> = gen_rnd_option::<f32>()
+            .into_iter()
+            .map(|x| x.map(|x| x - 0.5f32))
+            .collect();
+        test_generic(data.clone());
+        test_desc_generic(data.clone());
+        test_unstable_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_option_bool() {
+        let data = gen_rnd_option::<bool>();
+        test_generic(data.clone());
+        test_desc_generic(data);
+    }
+
+    #[test]
+    fn test_stable_option() {
+        let keys = gen_rnd_option::<i8>();
+        let mut data: Vec<(Option<i8>, usize)> = keys.into_iter().zip(0..CFG_N).collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by_key(|x| x.0);
+
+        data.rdxsort_by_key(|x| x.0);
+        assert!(data == data_sorted_ref, "sortd data does not match the reference!");
+    }
+
+    #[test]
+    fn test_empty_option() {
+        test_empty_generic::<Option<u8>>();
+    }
+
+    #[test]
+    fn test_single_option() {
+        test_single_generic::<Option<u8>>(None);
+    }
+}
+
+mod sub_by_key {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Record {
+        ts: u64,
+        id: usize,
+    }
+
+    #[test]
+    fn test_rnd_by_key() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<Record> = (0..CFG_N)
+            .map(|id| Record{ts: rng.gen::<u64>() % 100, id})
+            .collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by_key(|r| r.ts);
+
+        // stable sorting => equal keys keep their order (`id`)
+        data.rdxsort_by_key(|r| r.ts);
+        assert_eq!(data, data_sorted_ref);
+    }
+
+    #[test]
+    fn test_empty_by_key() {
+        let mut data: Vec<Record> = vec![];
+        data.rdxsort_by_key(|r| r.ts);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_rnd_by_key_float() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<(f32, u8)> = rng.gen_iter::<(f32, u8)>()
+            .take(CFG_N)
+            .map(|(x, y)| (x - 0.5f32, y))
+            .collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        data.rdxsort_by_key(|x| x.0);
+        assert_eq!(data, data_sorted_ref);
+    }
+
+    #[test]
+    fn test_rnd_by_key_float_stable() {
+        let mut rng = XorShiftRng::new_unseeded();
+        // few distinct negative keys, so many elements share the same key
+        let mut data: Vec<(f32, usize)> = (0..CFG_N)
+            .map(|id| (-((rng.gen::<u8>() % 4) as f32) - 0.5f32, id))
+            .collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        // stable sorting => equal keys keep their order (`id`)
+        data.rdxsort_by_key(|x| x.0);
+        assert_eq!(data, data_sorted_ref);
+    }
+
+    #[test]
+    fn test_rnd_by_key_reverse() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<Record> = (0..CFG_N)
+            .map(|id| Record{ts: rng.gen::<u64>() % 100, id})
+            .collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by_key(|r| Reverse(r.ts));
+
+        data.rdxsort_by_key(|r| Reverse(r.ts));
+        assert_eq!(data, data_sorted_ref);
+    }
+
+    #[test]
+    fn test_rnd_by_key_reverse_float() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<(f32, u8)> = rng.gen_iter::<(f32, u8)>()
+            .take(CFG_N)
+            .map(|(x, y)| (x - 0.5f32, y))
+            .collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+
+        data.rdxsort_by_key(|x| Reverse(x.0));
+        assert_eq!(data, data_sorted_ref);
+    }
+}
+
+mod sub_custom {
+    use super::*;
+
+    use std::cmp::Ordering;
+
+    /// Sorts descending by reversing the whole content in an extra round.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Desc(u8);
+
+    impl PartialOrd for Desc {
+        fn partial_cmp(&self, other: &Desc) -> Option<Ordering> {
+            other.0.partial_cmp(&self.0)
+        }
+    }
+
+    impl Rand for Desc {
+        fn rand<R: Rng>(rng: &mut R) -> Desc {
+            Desc(rng.gen())
+        }
+    }
+
+    impl MyHash for Desc {
+        fn hash_it<H>(&self, state: &mut H) where H: Hasher {
+            self.0.hash(state);
+        }
+    }
+
+    impl Rdx for Desc {
+        const CFG_NBUCKETS: usize = u8::CFG_NBUCKETS;
+
+        const CFG_NROUNDS: usize = u8::CFG_NROUNDS + 1;
+
+        fn get_bucket(&self, round: usize) -> Digit<Desc> {
+            if round < u8::CFG_NROUNDS {
+                self.0.get_bucket(round).cast()
+            } else {
+                Digit::new(0)
+            }
+        }
+
+        fn reverse(round: usize, _bucket: usize) -> bool {
+            round == u8::CFG_NROUNDS
+        }
+    }
+
+    /// Written for the old interface, using 4 bit digits.
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Legacy(u16);
+
+    impl Rand for Legacy {
+        fn rand<R: Rng>(rng: &mut R) -> Legacy {
+            Legacy(rng.gen())
+        }
+    }
+
+    impl MyHash for Legacy {
+        fn hash_it<H>(&self, state: &mut H) where H: Hasher {
+            self.0.hash(state);
+        }
+    }
+
+    rdx_legacy! {
+        impl Rdx for Legacy {
+            #[inline]
+            fn cfg_nbuckets() -> usize {
+                16
+            }
+
+            #[inline]
+            fn cfg_nrounds() -> usize {
+                u16::CFG_NROUNDS
+            }
+
+            #[inline]
+            fn get_bucket(&self, round: usize) -> usize {
+                ((self.0 >> (round * 4)) & 15) as usize
+            }
+
+            #[inline]
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
+            }
+        }
+    }
+
+    /// Returns buckets that are out of range.
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Broken(u8);
+
+    rdx_legacy! {
+        impl Rdx for Broken {
+            fn cfg_nbuckets() -> usize {
+                4
+            }
+
+            fn cfg_nrounds() -> usize {
+                1
+            }
+
+            fn get_bucket(&self, _round: usize) -> usize {
+                self.0 as usize
+            }
+
+            fn reverse(_round: usize, _bucket: usize) -> bool {
+                false
+            }
+        }
+    }
+
+    #[test]
+    fn test_rnd_custom() {
+        test_rnd_generic::<Desc>(vec![Desc(0), Desc(255)]);
+    }
+
+    #[test]
+    fn test_rnd_desc_custom() {
+        test_rnd_desc_generic::<Desc>(vec![Desc(0), Desc(255)]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_custom() {
+        test_rnd_unstable_generic::<Desc>(vec![Desc(0), Desc(255)]);
+    }
+
+    #[test]
+    fn test_rnd_legacy() {
+        test_rnd_generic::<Legacy>(vec![Legacy(0), Legacy(u16::MAX)]);
+    }
+
+    #[test]
+    fn test_rnd_desc_legacy() {
+        test_rnd_desc_generic::<Legacy>(vec![Legacy(0), Legacy(u16::MAX)]);
+    }
+
+    #[test]
+    fn test_rnd_unstable_legacy() {
+        test_rnd_unstable_generic::<Legacy>(vec![Legacy(0), Legacy(u16::MAX)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Your Rdx implementation returns a bucket >= CFG_NBUCKETS!")]
+    fn test_broken() {
+        let mut data = vec![Broken(1), Broken(4), Broken(0)];
+        data.rdxsort();
+    }
+
+    #[test]
+    #[should_panic(expected = "Your Rdx implementation returns a bucket >= CFG_NBUCKETS!")]
+    fn test_broken_unstable() {
+        let mut data = vec![Broken(1), Broken(4), Broken(0)];
+        data.rdxsort_unstable();
+    }
+}
+
+mod sub_radix {
+    use super::*;
+
+    fn test_rnd_radix_generic<T, K, F>(f: F)
+        where T: Clone + Ord + Rand,
+              K: Rdx,
+              F: FnMut(&T) -> K
+    {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<T> = rng.gen_iter::<T>().take(CFG_N).collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort();
+
+        data.rdxsort_by_key(f);
+        assert!(data == data_sorted_ref, "sortd data does not match the reference!");
+    }
+
+    #[test]
+    fn test_rnd_radix8() {
+        test_rnd_radix_generic::<u8, _, _>(|&x| Radix8(x));
+        test_rnd_radix_generic::<u16, _, _>(|&x| Radix8(x));
+        test_rnd_radix_generic::<u32, _, _>(|&x| Radix8(x));
+        test_rnd_radix_generic::<u64, _, _>(|&x| Radix8(x));
+        test_rnd_radix_generic::<usize, _, _>(|&x| Radix8(x));
+    }
+
+    #[test]
+    fn test_rnd_radix11() {
+        test_rnd_radix_generic::<u8, _, _>(|&x| Radix11(x));
+        test_rnd_radix_generic::<u16, _, _>(|&x| Radix11(x));
+        test_rnd_radix_generic::<u32, _, _>(|&x| Radix11(x));
+        test_rnd_radix_generic::<u64, _, _>(|&x| Radix11(x));
+        test_rnd_radix_generic::<usize, _, _>(|&x| Radix11(x));
+    }
+
+    #[test]
+    fn test_rnd_radix16() {
+        test_rnd_radix_generic::<u8, _, _>(|&x| Radix16(x));
+        test_rnd_radix_generic::<u16, _, _>(|&x| Radix16(x));
+        test_rnd_radix_generic::<u32, _, _>(|&x| Radix16(x));
+        test_rnd_radix_generic::<u64, _, _>(|&x| Radix16(x));
+        test_rnd_radix_generic::<usize, _, _>(|&x| Radix16(x));
+    }
+
+    #[test]
+    fn test_rnd_radix8_array() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<[u8; 48]> = (0..CFG_N)
+            .map(|_| {
+                let mut x = [0u8; 48];
+                rng.fill_bytes(&mut x);
+                // few distinct values in the first bytes, so later bytes matter as well
+                x[0] %= 2;
+                x[1] %= 2;
+                x
+            })
+            .collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort();
+
+        data.rdxsort_by_key(|&x| Radix8(x));
+        assert!(data == data_sorted_ref, "sortd data does not match the reference!");
+    }
+
+    #[test]
+    fn test_rnd_radix_unstable() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<u64> = rng.gen_iter::<u64>().take(CFG_N).collect();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort();
+
+        let mut data: Vec<Radix11<u64>> = data.into_iter().map(Radix11).collect();
+        data.rdxsort_unstable();
+        assert!(data.into_iter().map(|x| x.0).collect::<Vec<u64>>() == data_sorted_ref);
+    }
+}
+
+mod sub_argsort {
+    use super::*;
+
+    fn test_rnd_argsort_generic<T>(data: Vec<T>)
+        where T: Clone + PartialOrd + Rdx
+    {
+        let mut indices_ref: Vec<usize> = (0..data.len()).collect();
+        indices_ref.sort_by(|&a, &b| data[a].partial_cmp(&data[b]).unwrap());
+
+        let indices = data.rdxargsort();
+        assert!(indices == indices_ref, "permutation does not match the reference!");
+    }
+
+    #[test]
+    fn test_rnd_argsort_u64() {
+        let mut rng = XorShiftRng::new_unseeded();
+        // many equal elements to test stability
+        let data: Vec<u64> = rng.gen_iter::<u64>().take(CFG_N).map(|x| x % 100).collect();
+        test_rnd_argsort_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_argsort_f32() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<f32> = rng.gen_iter::<f32>().take(CFG_N).map(|x| x - 0.5f32).collect();
+        test_rnd_argsort_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_argsort_tuple() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<(bool, i8)> = rng.gen_iter::<(bool, i8)>().take(CFG_N).collect();
+        test_rnd_argsort_generic(data);
+    }
+
+    #[test]
+    fn test_empty_argsort() {
+        let data: Vec<u32> = vec![];
+        assert!(data.rdxargsort().is_empty());
+    }
+}
+
+mod sub_with {
+    use super::*;
+
+    /// Payload that implements neither `Rdx` nor `Clone`.
+    #[derive(Debug, PartialEq)]
+    struct Payload(usize);
+
+    #[test]
+    fn test_rnd_with() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut keys: Vec<u64> = rng.gen_iter::<u64>().take(CFG_N).map(|x| x % 100).collect();
+        let mut payload_a: Vec<Payload> = (0..CFG_N).map(Payload).collect();
+        let mut payload_b: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+        let mut payload_c: Vec<usize> = (0..CFG_N).collect();
+
+        let mut reference: Vec<(u64, usize)> = keys.iter().cloned().zip(0..CFG_N).collect();
+        reference.sort_by_key(|x| x.0);
+
+        keys.rdxsort_with(&mut [&mut payload_a, &mut payload_b, &mut &mut payload_c[..]]);
+        for (i, &(k, j)) in reference.iter().enumerate() {
+            assert_eq!(keys[i], k);
+            assert_eq!(payload_a[i], Payload(j));
+            assert_eq!(payload_b[i], k.to_string());
+            assert_eq!(payload_c[i], j);
+        }
+    }
+
+    #[test]
+    fn test_empty_with() {
+        let mut keys: Vec<u64> = vec![];
+        let mut payload: Vec<Payload> = vec![];
+        keys.rdxsort_with(&mut [&mut payload]);
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrong_len_with() {
+        let mut keys: Vec<u64> = vec![3, 2, 1];
+        let mut payload: Vec<Payload> = vec![Payload(0)];
+        keys.rdxsort_with(&mut [&mut payload]);
+    }
+}
+
+mod sub_sorter {
+    use super::*;
+
+    #[test]
+    fn test_rnd_sorter() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut sorter = RdxSorter::new();
+        for n in [0, 1, 2, 10, 1_000, 7, CFG_N, 100].iter() {
+            let data: Vec<i32> = rng.gen_iter::<i32>().take(*n).collect();
+
+            let mut reference = data.clone();
+            reference.rdxsort();
+            let mut sorted = data.clone();
+            sorter.sort(&mut sorted);
+            assert_eq!(sorted, reference);
+
+            let mut reference = data.clone();
+            reference.rdxsort_desc();
+            let mut sorted = data;
+            sorter.sort_desc(&mut sorted);
+            assert_eq!(sorted, reference);
+        }
+    }
+
+    #[test]
+    fn test_rnd_stable_sorter() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut sorter = RdxSorter::with_capacity(CFG_N);
+        for _ in 0..CFG_M {
+            let data: Vec<(u8, f32)> = (0..CFG_N / CFG_M)
+                .map(|_| (rng.gen::<u8>() % 4, rng.gen::<f32>() - 0.5f32))
+                .collect();
+            let mut reference = data.clone();
+            reference.rdxsort();
+            let mut sorted = data;
+            sorter.sort(&mut sorted);
+            assert_eq!(sorted, reference);
+        }
+    }
+
+    #[test]
+    fn test_rnd_unit_sorter() {
+        let mut sorter = RdxSorter::default();
+        let mut data = vec![(); 10];
+        sorter.sort(&mut data);
+        sorter.sort_desc(&mut data);
+    }
+}
+
+mod sub_small {
+    use super::*;
+
+    /// Sorts prefixes of the data on their own, which uses the MSD variant, and checks that their
+    /// order matches the one within the sorted full data, which uses LSD.
+    fn test_small_generic<K, F>(f: F)
+        where K: Copy + Rdx,
+              F: Fn(&mut XorShiftRng) -> K
+    {
+        let mut rng = XorShiftRng::new_unseeded();
+        let data: Vec<(K, usize)> = (0..CFG_N).map(|id| (f(&mut rng), id)).collect();
+        let mut sorted = data.clone();
+        sorted.rdxsort_by_key(|x| x.0);
+
+        for &n in [CFG_N_SMALL, 100, 17, 16, 2].iter() {
+            let mut small = data[..n].to_vec();
+            small.rdxsort_by_key(|x| x.0);
+            let is: Vec<usize> = small.iter().map(|x| x.1).collect();
+            let reference: Vec<usize> = sorted.iter().map(|x| x.1).filter(|&id| id < n).collect();
+            assert_eq!(is, reference);
+        }
+    }
+
+    #[test]
+    fn test_rnd_small_i64() {
+        test_small_generic(|rng| rng.gen::<i64>() % 1000);
+        test_small_generic(|rng| Reverse(rng.gen::<i64>() % 1000));
+    }
+
+    #[test]
+    fn test_rnd_small_f32() {
+        // few distinct keys, so many elements share the same (maybe reversed) buckets
+        test_small_generic(|rng| ((rng.gen::<u8>() % 8) as f32) - 3.5f32);
+        test_small_generic(|rng| Reverse(((rng.gen::<u8>() % 8) as f32) - 3.5f32));
+    }
+
+    #[test]
+    fn test_rnd_small_odd() {
+        test_small_generic(|rng| Odd(rng.gen::<u16>() & 0x0707));
+        test_small_generic(|rng| Reverse(Odd(rng.gen::<u16>() & 0x0707)));
+    }
+
+    #[test]
+    fn test_rnd_small_tuple() {
+        test_small_generic(|rng| (Reverse(rng.gen::<bool>()), rng.gen::<u8>() % 4));
+    }
+}
+
+mod sub_move {
+    use super::*;
+
+    use std::cell::Cell;
+    use std::panic;
+    use std::rc::Rc;
+
+    /// Neither `Clone` nor `Copy`, counts how often it is dropped.
+    struct Tracked {
+        key: u16,
+        poisoned: bool,
+        calls: Cell<usize>,
+        drops: Rc<Cell<usize>>,
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    impl Rdx for Tracked {
+        const CFG_NBUCKETS: usize = 16;
+
+        const CFG_NROUNDS: usize = 4;
+
+        fn get_bucket(&self, round: usize) -> Digit<Tracked> {
+            // inject a panic after the histogram prepass
+            self.calls.set(self.calls.get() + 1);
+            assert!(!self.poisoned || self.calls.get() <= Self::CFG_NROUNDS + 2,
+                    "poisoned element");
+            Digit::new(((self.key >> (round * 4)) & 15) as usize)
+        }
+
+        fn reverse(_round: usize, _bucket: usize) -> bool {
+            false
+        }
+    }
+
+    fn gen_tracked(drops: &Rc<Cell<usize>>) -> Vec<Tracked> {
+        let mut rng = XorShiftRng::new_unseeded();
+        rng.gen_iter::<u16>()
+            .take(CFG_N)
+            .map(|key| {
+                Tracked {
+                    key,
+                    poisoned: false,
+                    calls: Cell::new(0),
+                    drops: drops.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn keys(data: &[Tracked]) -> Vec<u16> {
+        data.iter().map(|x| x.key).collect()
+    }
+
+    #[test]
+    fn test_rnd_move() {
+        let drops = Rc::new(Cell::new(0));
+        let mut data = gen_tracked(&drops);
+        let mut reference = keys(&data);
+        reference.sort();
+
+        data.rdxsort();
+        assert!(keys(&data) == reference);
+        assert_eq!(drops.get(), 0);
+        drop(data);
+        assert_eq!(drops.get(), CFG_N);
+    }
+
+    #[test]
+    fn test_rnd_desc_move() {
+        let drops = Rc::new(Cell::new(0));
+        let mut data = gen_tracked(&drops);
+        let mut reference = keys(&data);
+        reference.sort_by(|a, b| b.cmp(a));
+
+        data.rdxsort_desc();
+        assert!(keys(&data) == reference);
+        drop(data);
+        assert_eq!(drops.get(), CFG_N);
+    }
+
+    #[test]
+    fn test_rnd_by_key_move() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let mut data: Vec<(u64, String)> = (0..CFG_N)
+            .map(|i| (rng.gen::<u64>() % 100, i.to_string()))
+            .collect();
+        let mut reference = data.clone();
+        reference.sort_by_key(|x| x.0);
+
+        data.rdxsort_by_key(|x| x.0);
+        assert!(data == reference);
+    }
+
+    #[test]
+    fn test_panic_move() {
+        // panic while moving the data from the scratch buffer back to `data` (second round) and
+        // while moving it to the scratch buffer (third round)
+        for &skipped in [1, 0].iter() {
+            let drops = Rc::new(Cell::new(0));
+            let mut data = gen_tracked(&drops);
+            data[CFG_N / 2].poisoned = true;
+            data[CFG_N / 2].calls.set(skipped);
+            let mut reference = keys(&data);
+            reference.sort();
+
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| data.rdxsort()));
+            assert!(result.is_err());
+
+            // every element is still there exactly once
+            let mut sorted = keys(&data);
+            sorted.sort();
+            assert!(sorted == reference);
+            assert_eq!(drops.get(), 0);
+            drop(data);
+            assert_eq!(drops.get(), CFG_N);
+        }
+    }
+
+    #[test]
+    fn test_panic_move_small() {
+        // panic during the first histogram pass and during the insertion sort of the MSD variant,
+        // which takes over after two rounds
+        for &skipped in [6, 2].iter() {
+            let drops = Rc::new(Cell::new(0));
+            let mut data = gen_tracked(&drops);
+            data.truncate(CFG_N_SMALL);
+            drops.set(0);
+            data[CFG_N_SMALL / 2].poisoned = true;
+            data[CFG_N_SMALL / 2].calls.set(skipped);
+            let mut reference = keys(&data);
+            reference.sort();
+
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| data.rdxsort()));
+            assert!(result.is_err());
+
+            // every element is still there exactly once
+            let mut sorted = keys(&data);
+            sorted.sort();
+            assert!(sorted == reference);
+            assert_eq!(drops.get(), 0);
+            drop(data);
+            assert_eq!(drops.get(), CFG_N_SMALL);
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_move() {
+        let drops = Rc::new(Cell::new(0));
+        let mut data = gen_tracked(&drops);
+        let mut calls = 0;
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            data.rdxsort_by_key(|x| {
+                // different keys after the histogram prepass
+                calls += 1;
+                if calls > CFG_N * 2 { 0u16 } else { x.key }
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(data.len(), CFG_N);
+        drop(data);
+        assert_eq!(drops.get(), CFG_N);
+    }
+
+    #[test]
+    fn test_inconsistent_move_small() {
+        let drops = Rc::new(Cell::new(0));
+        let mut data = gen_tracked(&drops);
+        data.truncate(CFG_N_SMALL);
+        drops.set(0);
+        let mut reference = keys(&data);
+        reference.sort();
+        let mut calls = 0;
+        data.rdxsort_by_key(|x| {
+            // different keys after the first histogram pass of the MSD variant, which only
+            // garbles the order since it moves the data by the stored digits
+            calls += 1;
+            if calls > CFG_N_SMALL * 2 { 0u16 } else { x.key }
+        });
+
+        // every element is still there exactly once
+        let mut sorted = keys(&data);
+        sorted.sort();
+        assert!(sorted == reference);
+        assert_eq!(drops.get(), 0);
+        drop(data);
+        assert_eq!(drops.get(), CFG_N_SMALL);
+    }
+}
+
+mod sub_total {
+    use super::*;
+
+    use std::f64;
+
+    fn gen_rnd_with_nans() -> Vec<f64> {
+        let mut rng = XorShiftRng::new_unseeded();
+        let special = [f64::NAN, -f64::NAN, f64::INFINITY, -f64::INFINITY, 0f64, -0f64,
+                       f64::from_bits(0x7ff0_0000_0000_0001),
+                       f64::from_bits(0xfff8_0000_0000_0002)];
+        (0..CFG_N)
+            .map(|i| if i % 7 == 0 {
+                special[(i / 7) % special.len()]
+            } else {
+                rng.gen::<f64>() - 0.5f64
+            })
+            .collect()
+    }
+
+    fn assert_same_bits(data: &[f64], reference: &[f64]) {
+        assert!(data.len() == reference.len(), "sorted data has wrong lenght!");
+        for (x, y) in data.iter().zip(reference.iter()) {
+            assert!(x.to_bits() == y.to_bits(), "sortd data does not match the reference!");
+        }
+    }
+
+    #[test]
+    fn test_rnd_total_ord() {
+        let mut data = gen_rnd_with_nans();
+        let mut reference = data.clone();
+        reference.sort_by(|a, b| a.total_cmp(b));
+
+        data.rdxsort_by_key(|&x| TotalOrd(x));
+        assert_same_bits(&data, &reference);
+        assert!(data[0].is_nan() && data[0].is_sign_negative());
+        assert!(data[CFG_N - 1].is_nan() && data[CFG_N - 1].is_sign_positive());
+    }
+
+    #[test]
+    fn test_rnd_total_ord_unstable() {
+        let mut data: Vec<TotalOrd<f32>> = gen_rnd_with_nans()
+            .into_iter()
+            .map(|x| TotalOrd(x as f32))
+            .collect();
+        let mut reference = data.clone();
+        reference.sort();
+
+        data.rdxsort_unstable();
+        assert!(data == reference, "sortd data does not match the reference!");
+    }
+
+    #[test]
+    fn test_rnd_nan_last() {
+        let mut data = gen_rnd_with_nans();
+        let mut reference = data.clone();
+        reference.sort_by(|a, b| a.is_nan().cmp(&b.is_nan()).then(a.total_cmp(b)));
+
+        data.rdxsort_by_key(|&x| NanLast(x));
+        assert_same_bits(&data, &reference);
+        let first_nan = data.iter().position(|x| x.is_nan()).unwrap();
+        assert!(data[first_nan..].iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_zeros_total_ord() {
+        let mut data = vec![0f64, -0f64, 0f64, -0f64];
+        data.rdxsort_by_key(|&x| TotalOrd(x));
+        assert!(data[0].is_sign_negative() && data[1].is_sign_negative());
+        assert!(data[2].is_sign_positive() && data[3].is_sign_positive());
+    }
+
+    #[test]
+    #[should_panic(expected = "Sorting of NaNs is not implemented!")]
+    fn test_nan_panics() {
+        let mut data = vec![1f64, f64::NAN, 0f64];
+        data.rdxsort();
+    }
+}
+
+mod sub_bytes {
+    use super::*;
+
+    /// Strings with lots of shared prefixes, different lengths and some multi-byte characters.
+    fn gen_rnd_strings() -> Vec<String> {
+        let alphabet = ['a', 'b', 'c', 'z', 'ä', 'ß', '€', '😀'];
+        let mut rng = XorShiftRng::new_unseeded();
+        (0..CFG_N)
+            .map(|_| {
+                let len = rng.gen_range(0, 12);
+                (0..len).map(|_| *rng.choose(&alphabet).unwrap()).collect()
+            })
+            .collect()
+    }
+
+    fn test_bytes_generic<T>(data: Vec<T>)
+        where T: Clone + Ord,
+              Vec<T>: RdxSort
+    {
+        test_generic(data.clone());
+
+        let mut data_desc = data.clone();
+        let mut data_sorted_ref = data.clone();
+        data_sorted_ref.sort_by(|a, b| b.cmp(a));
+        data_desc.rdxsort_desc();
+        assert!(data_desc == data_sorted_ref, "sortd data does not match the reference!");
+
+        let mut data_unstable = data;
+        data_sorted_ref.reverse();
+        data_unstable.rdxsort_unstable();
+        assert!(data_unstable == data_sorted_ref, "sortd data does not match the reference!");
+    }
+
+    #[test]
+    fn test_rnd_string() {
+        test_bytes_generic(gen_rnd_strings());
+    }
+
+    #[test]
+    fn test_rnd_str() {
+        let strings = gen_rnd_strings();
+        test_bytes_generic(strings.iter().map(|s| s.as_str()).collect::<Vec<&str>>());
+    }
+
+    #[test]
+    fn test_rnd_vec_u8() {
+        let data = gen_rnd_strings().into_iter().map(|s| s.into_bytes()).collect::<Vec<Vec<u8>>>();
+        test_bytes_generic(data);
+    }
+
+    #[test]
+    fn test_rnd_slice_u8() {
+        let mut rng = XorShiftRng::new_unseeded();
+        let bytes: Vec<u8> = rng.gen_iter::<u8>().take(CFG_N).collect();
+        let data: Vec<&[u8]> = (0..CFG_N)
+            .map(|i| &bytes[i..(i + rng.gen_range(0, 5)).min(CFG_N)])
+            .collect();
+        test_bytes_generic(data);
+    }
+
+    #[test]
+    fn test_prefixes_string() {
+        let mut data: Vec<String> = (0..2_000).rev().map(|i| "x".repeat(i)).collect();
+        data.push("w".to_string());
+        data.push("y".to_string());
+        test_bytes_generic(data);
+    }
+
+    #[test]
+    fn test_stable_str() {
+        let base = "ab ab ab b a";
+        let mut data: Vec<&str> = base.split(' ').collect();
+        data.rdxsort();
+        assert!(data == vec!["a", "ab", "ab", "ab", "b"]);
+
+        // equal keys keep their order, which is visible via their addresses
+        let addrs: Vec<*const u8> = data[1..4].iter().map(|s| s.as_ptr()).collect();
+        let mut sorted_addrs = addrs.clone();
+        sorted_addrs.sort();
+        assert!(addrs == sorted_addrs);
+    }
+
+    #[test]
+    fn test_empty_string() {
+        test_empty_generic::<String>();
+    }
+
+    #[test]
+    fn test_single_string() {
+        test_single_generic::<String>("foo".to_string());
+    }
+
+    #[test]
+    fn test_empty_vec_u8() {
+        test_empty_generic::<Vec<u8>>();
+    }
+
+    #[test]
+    fn test_single_vec_u8() {
+        test_single_generic::<Vec<u8>>(vec![]);
+    }
 }
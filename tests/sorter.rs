@@ -0,0 +1,49 @@
+extern crate rand;
+extern crate rdxsort;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::{Rng, XorShiftRng};
+
+use rdxsort::*;
+
+/// Counts all allocations of the test binary, so this file must only contain a single test.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+fn test_sorter_does_not_allocate() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let batches: Vec<Vec<u32>> = (0..100)
+        .map(|i| rng.gen_iter::<u32>().take(i % 50).collect())
+        .collect();
+    let mut sorter = RdxSorter::with_capacity(64);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    for mut batch in batches {
+        sorter.sort(&mut batch);
+        sorter.sort_desc(&mut batch);
+    }
+    assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), before);
+}
@@ -1,7 +1,19 @@
+extern crate rand;
 extern crate rdxsort;
 
+mod common;
+
+use std::collections::BTreeSet;
+use std::ops::Bound;
+use std::sync::Arc;
+use std::thread;
+
+use rand::{Rng, XorShiftRng};
+
 use rdxsort::*;
 
+use common::{Odd, Tagged};
+
 #[test]
 fn test_insert() {
     let mut tree: RdxTree<u32> = RdxTree::new();
@@ -12,7 +24,7 @@ fn test_insert() {
     tree.insert(0);
 
     let should = vec![0, 1, 2, 22, 1024];
-    let is: Vec<u32> = tree.iter().collect();
+    let is: Vec<u32> = tree.iter().cloned().collect();
     assert_eq!(should, is);
     assert_eq!(tree.nnodes(), (4, 3, 5, 56));
 }
@@ -29,7 +41,361 @@ fn test_insert_float() {
     tree.insert(0f32);
 
     let should = vec![-1024f32, -1f32, 0f32, 1f32, 2f32, 22f32, 1024f32];
-    let is: Vec<f32> = tree.iter().collect();
+    let is: Vec<f32> = tree.iter().cloned().collect();
     assert_eq!(should, is);
     assert_eq!(tree.nnodes(), (4, 7, 7, 54));
 }
+
+#[test]
+fn test_len() {
+    let mut tree: RdxTree<u32> = RdxTree::new();
+    assert!(tree.is_empty());
+    assert!(tree.insert(1));
+    assert!(tree.insert(1024));
+    assert!(!tree.insert(1));
+    assert_eq!(tree.len(), 2);
+    assert!(!tree.is_empty());
+
+    tree.clear();
+    assert!(tree.is_empty());
+    assert_eq!(tree.iter().count(), 0);
+    assert_eq!(tree.nnodes(), RdxTree::<u32>::new().nnodes());
+}
+
+#[test]
+fn test_lookup() {
+    let mut tree: RdxTree<i32> = RdxTree::new();
+    tree.insert(-5);
+    tree.insert(22);
+    tree.insert(23);
+
+    assert!(tree.contains(&-5));
+    assert!(tree.contains(&23));
+    assert!(!tree.contains(&5));
+    assert!(!tree.contains(&24));
+    assert_eq!(tree.get(&22), Some(&22));
+    assert_eq!(tree.get(&21), None);
+    assert_eq!(tree.replace(22), Some(22));
+    assert_eq!(tree.replace(21), None);
+    assert_eq!(tree.len(), 4);
+}
+
+#[test]
+fn test_insert_replace() {
+    let mut tree: RdxTree<Tagged> = RdxTree::new();
+    assert!(tree.insert(Tagged(7, 'a')));
+
+    // like `BTreeSet`, `insert` keeps the stored element
+    assert!(!tree.insert(Tagged(7, 'b')));
+    assert_eq!(tree.get(&Tagged(7, 'x')), Some(&Tagged(7, 'a')));
+
+    // while `replace` overwrites it
+    assert_eq!(tree.replace(Tagged(7, 'c')), Some(Tagged(7, 'a')));
+    assert_eq!(tree.get(&Tagged(7, 'x')), Some(&Tagged(7, 'c')));
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_remove() {
+    let mut tree: RdxTree<u32> = RdxTree::new();
+    tree.insert(1);
+    tree.insert(22);
+    tree.insert(2);
+    tree.insert(1024);
+    tree.insert(0);
+
+    assert!(tree.remove(&22));
+    assert!(!tree.remove(&22));
+    assert!(!tree.remove(&23));
+    assert_eq!(tree.take(&1024), Some(1024));
+    assert_eq!(tree.take(&1024), None);
+    assert_eq!(tree.len(), 3);
+
+    let is: Vec<u32> = tree.iter().cloned().collect();
+    assert_eq!(is, vec![0, 1, 2]);
+
+    // removing the last elements of a branch collapses the tree again
+    let mut reference: RdxTree<u32> = RdxTree::new();
+    reference.insert(0);
+    reference.insert(1);
+    reference.insert(2);
+    assert_eq!(tree.nnodes(), reference.nnodes());
+
+    for x in 0..3 {
+        assert!(tree.remove(&x));
+    }
+    assert!(tree.is_empty());
+    assert_eq!(tree.nnodes(), RdxTree::<u32>::new().nnodes());
+}
+
+#[test]
+fn test_rnd_remove() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut tree: RdxTree<u32> = RdxTree::new();
+    let mut set = BTreeSet::new();
+    for _ in 0..10_000 {
+        // small keys, so some of them collide
+        let x = rng.gen::<u32>() % 100_000;
+        assert_eq!(tree.insert(x), set.insert(x));
+    }
+    for _ in 0..10_000 {
+        let x = rng.gen::<u32>() % 100_000;
+        assert_eq!(tree.remove(&x), set.remove(&x));
+        assert_eq!(tree.len(), set.len());
+    }
+
+    let is: Vec<u32> = tree.iter().cloned().collect();
+    let should: Vec<u32> = set.iter().cloned().collect();
+    assert_eq!(is, should);
+    for x in 0..100_000 {
+        assert_eq!(tree.contains(&x), set.contains(&x));
+    }
+
+    // the tree has the same shape as if the remaining elements were inserted directly
+    let mut reference: RdxTree<u32> = RdxTree::new();
+    for x in set {
+        reference.insert(x);
+    }
+    assert_eq!(tree.nnodes(), reference.nnodes());
+}
+
+#[test]
+fn test_remove_float() {
+    let mut tree: RdxTree<f32> = RdxTree::new();
+    for &x in &[1f32, 22f32, -1024f32, -1f32, 0f32] {
+        tree.insert(x);
+    }
+    assert!(!tree.remove(&-0f32));
+    assert!(tree.remove(&-1f32));
+    assert!(tree.remove(&22f32));
+
+    let is: Vec<f32> = tree.iter().cloned().collect();
+    assert_eq!(is, vec![-1024f32, 0f32, 1f32]);
+}
+
+#[test]
+fn test_first_last() {
+    let mut tree: RdxTree<i32> = RdxTree::new();
+    assert_eq!(tree.first(), None);
+    assert_eq!(tree.last(), None);
+
+    tree.insert(3);
+    assert_eq!(tree.first(), Some(&3));
+    assert_eq!(tree.last(), Some(&3));
+
+    for &x in &[-7, 100, -1, 0] {
+        tree.insert(x);
+    }
+    assert_eq!(tree.first(), Some(&-7));
+    assert_eq!(tree.last(), Some(&100));
+}
+
+#[test]
+fn test_floor_ceiling() {
+    let mut tree: RdxTree<f32> = RdxTree::new();
+    for &x in &[-1024f32, -1.5f32, 0f32, 2f32, 22f32] {
+        tree.insert(x);
+    }
+
+    assert_eq!(tree.floor(&-2000f32), None);
+    assert_eq!(tree.floor(&-1024f32), Some(&-1024f32));
+    assert_eq!(tree.floor(&-1f32), Some(&-1.5f32));
+    assert_eq!(tree.floor(&1f32), Some(&0f32));
+    assert_eq!(tree.floor(&1000f32), Some(&22f32));
+
+    assert_eq!(tree.ceiling(&-2000f32), Some(&-1024f32));
+    assert_eq!(tree.ceiling(&-2f32), Some(&-1.5f32));
+    assert_eq!(tree.ceiling(&-1f32), Some(&0f32));
+    assert_eq!(tree.ceiling(&2f32), Some(&2f32));
+    assert_eq!(tree.ceiling(&23f32), None);
+}
+
+#[test]
+fn test_range() {
+    let mut tree: RdxTree<i16> = RdxTree::new();
+    for x in -10..10 {
+        tree.insert(x * 3);
+    }
+
+    let is: Vec<i16> = tree.range(-7..5).cloned().collect();
+    assert_eq!(is, vec![-6, -3, 0, 3]);
+    let is: Vec<i16> = tree.range(-6..=6).cloned().collect();
+    assert_eq!(is, vec![-6, -3, 0, 3, 6]);
+    let is: Vec<i16> = tree.range(20..).cloned().collect();
+    assert_eq!(is, vec![21, 24, 27]);
+    let is: Vec<i16> = tree.range(..-24).rev().cloned().collect();
+    assert_eq!(is, vec![-27, -30]);
+    assert_eq!(tree.range(..).count(), 20);
+
+    // empty ranges
+    assert_eq!(tree.range(1..3).count(), 0);
+    assert_eq!(tree.range(3..3).count(), 0);
+    assert_eq!(tree.range((Bound::Included(9), Bound::Excluded(0))).count(), 0);
+    assert_eq!(tree.range(100..).count(), 0);
+
+    // both ends meet in the middle
+    let mut range = tree.range(-3..=3);
+    assert_eq!(range.next(), Some(&-3));
+    assert_eq!(range.next_back(), Some(&3));
+    assert_eq!(range.next_back(), Some(&0));
+    assert_eq!(range.next(), None);
+    assert_eq!(range.next_back(), None);
+}
+
+#[test]
+fn test_rnd_range() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut tree: RdxTree<i32> = RdxTree::new();
+    let mut set = BTreeSet::new();
+    for _ in 0..1_000 {
+        let x = rng.gen::<i32>() % 100_000;
+        tree.insert(x);
+        set.insert(x);
+    }
+
+    for _ in 0..1_000 {
+        let a = rng.gen::<i32>() % 100_000;
+        let b = rng.gen::<i32>() % 100_000;
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+
+        let is: Vec<i32> = tree.range(a..b).cloned().collect();
+        let should: Vec<i32> = set.range(a..b).cloned().collect();
+        assert_eq!(is, should);
+        let is: Vec<i32> = tree.range(a..=b).rev().cloned().collect();
+        let should: Vec<i32> = set.range(a..=b).rev().cloned().collect();
+        assert_eq!(is, should);
+
+        assert_eq!(tree.floor(&a), set.range(..=a).next_back());
+        assert_eq!(tree.ceiling(&a), set.range(a..).next());
+    }
+}
+
+fn assert_send_sync<T>()
+    where T: Send + Sync
+{
+}
+
+#[test]
+fn test_send_sync() {
+    assert_send_sync::<RdxTree<u32>>();
+    assert_send_sync::<RdxTree<f64>>();
+    assert_send_sync::<RdxTreeIter<'static, u32>>();
+    assert_send_sync::<RdxTreeRange<'static, u32>>();
+    assert_send_sync::<RdxMap<u32, String>>();
+}
+
+#[test]
+fn test_concurrent_iter() {
+    let mut tree: RdxTree<u32> = RdxTree::new();
+    for x in 0..10_000 {
+        tree.insert(x * 7);
+    }
+
+    let tree = Arc::new(tree);
+    let handles: Vec<_> = (0..4u32)
+        .map(|i| {
+            let tree = tree.clone();
+            thread::spawn(move || {
+                let all: Vec<u32> = tree.iter().cloned().collect();
+                let range: Vec<u32> = tree.range(i * 1_000..(i + 1) * 1_000).cloned().collect();
+                (all, range)
+            })
+        })
+        .collect();
+
+    let should: Vec<u32> = (0..10_000).map(|x| x * 7).collect();
+    for (i, handle) in handles.into_iter().enumerate() {
+        let (all, range) = handle.join().unwrap();
+        assert_eq!(all, should);
+        let i = i as u32;
+        let should_range: Vec<u32> =
+            should.iter().cloned().filter(|&x| x >= i * 1_000 && x < (i + 1) * 1_000).collect();
+        assert_eq!(range, should_range);
+    }
+}
+
+#[test]
+fn test_iter_double_ended() {
+    let mut tree: RdxTree<i32> = RdxTree::new();
+    for &x in &[5, -3, 100, 0, -1000, 7] {
+        tree.insert(x);
+    }
+
+    let is: Vec<i32> = tree.iter().rev().cloned().collect();
+    assert_eq!(is, vec![100, 7, 5, 0, -3, -1000]);
+
+    let mut iter = tree.iter();
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.next(), Some(&-1000));
+    assert_eq!(iter.next_back(), Some(&100));
+    assert_eq!(iter.next_back(), Some(&7));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some(&-3));
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let is: Vec<i32> = (&tree).into_iter().cloned().collect();
+    assert_eq!(is.len(), 6);
+}
+
+/// Key type that cannot be cloned.
+#[derive(Debug, PartialEq)]
+struct Big(u16, Vec<u8>);
+
+impl Rdx for Big {
+    const CFG_NBUCKETS: usize = <u16 as Rdx>::CFG_NBUCKETS;
+
+    const CFG_NROUNDS: usize = <u16 as Rdx>::CFG_NROUNDS;
+
+    fn get_bucket(&self, round: usize) -> Digit<Big> {
+        self.0.get_bucket(round).cast()
+    }
+
+    fn reverse(round: usize, bucket: usize) -> bool {
+        <u16 as Rdx>::reverse(round, bucket)
+    }
+}
+
+#[test]
+fn test_into_iter() {
+    let mut tree: RdxTree<Big> = RdxTree::new();
+    for &x in &[300u16, 2, 1, 65535] {
+        tree.insert(Big(x, vec![x as u8; 100]));
+    }
+    assert_eq!(tree.first(), Some(&Big(1, vec![1; 100])));
+
+    let mut iter = tree.into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next_back(), Some(Big(65535, vec![255; 100])));
+    let is: Vec<u16> = iter.map(|x| x.0).collect();
+    assert_eq!(is, vec![1, 2, 300]);
+
+    let mut tree: RdxTree<f32> = RdxTree::new();
+    for &x in &[1f32, -1f32, 0f32, -1024f32, 22f32] {
+        tree.insert(x);
+    }
+    let is: Vec<f32> = tree.into_iter().collect();
+    assert_eq!(is, vec![-1024f32, -1f32, 0f32, 1f32, 22f32]);
+}
+
+#[test]
+fn test_rnd_reverse() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut tree: RdxTree<Odd> = RdxTree::new();
+    let mut data: Vec<Odd> = Vec::new();
+    for _ in 0..1_000 {
+        // sparse digits, so parts of the tree get pruned
+        let x = Odd(rng.gen::<u16>() & 0x0f0f);
+        if tree.insert(x) {
+            data.push(x);
+        }
+    }
+
+    // the tree order must match the sorting order
+    data.rdxsort();
+    let is: Vec<Odd> = tree.iter().cloned().collect();
+    assert_eq!(is, data);
+}
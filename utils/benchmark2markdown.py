@@ -14,7 +14,8 @@ with open(fin) as f:
         if line.startswith(prefix):
             content = line[len(prefix):]
             s, t, a = content.split(' ')[0].split('_')
-            x = int(content.split(':')[1].strip().split(' ')[0].replace(',', ''))
+            # newer versions of the bench harness print fractions of nanoseconds
+            x = int(round(float(content.split(':')[1].strip().split(' ')[0].replace(',', ''))))
 
             if s not in table:
                 table[s] = {}